@@ -0,0 +1,80 @@
+//! Times parsing of a realistic response with 20 headers.
+//!
+//! Compare the byte-by-byte header scan with the `memchr` one by saving the
+//! first run as a baseline:
+//!
+//! ```text
+//! cargo bench -- --save-baseline scalar
+//! cargo bench --features fast-scan -- --baseline scalar
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use http_parser::{
+    parse_request, parse_request_slices, parse_response, parse_response_raw, EMPTY_HEADER,
+};
+
+const RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\n\
+    Date: Mon, 27 Jul 2009 12:28:53 GMT\r\n\
+    Server: Apache/2.4.41 (Ubuntu)\r\n\
+    Last-Modified: Wed, 22 Jul 2009 19:15:56 GMT\r\n\
+    ETag: \"34aa387-d-1568eb00\"\r\n\
+    Accept-Ranges: bytes\r\n\
+    Content-Length: 13\r\n\
+    Vary: Accept-Encoding, User-Agent\r\n\
+    Content-Type: text/html; charset=utf-8\r\n\
+    Cache-Control: public, max-age=31536000, immutable\r\n\
+    Expires: Thu, 27 Jul 2010 12:28:53 GMT\r\n\
+    X-Frame-Options: SAMEORIGIN\r\n\
+    X-Content-Type-Options: nosniff\r\n\
+    X-XSS-Protection: 1; mode=block\r\n\
+    Strict-Transport-Security: max-age=63072000; includeSubDomains; preload\r\n\
+    Content-Security-Policy: default-src 'self'; img-src https://*; child-src 'none'\r\n\
+    Set-Cookie: sessionid=38afes7a8; HttpOnly; Path=/; Secure\r\n\
+    Set-Cookie: lang=en-US; Path=/; Domain=example.com\r\n\
+    Referrer-Policy: strict-origin-when-cross-origin\r\n\
+    Connection: keep-alive\r\n\
+    Keep-Alive: timeout=5, max=1000\r\n\
+    \r\n\
+    Hello, world!";
+
+const REQUEST: &[u8] = b"GET /wp-content/uploads/2010/03/hello-kitty-darth-vader-pink.jpg HTTP/1.1\r\n\
+    Host: www.kittyhell.com\r\n\
+    User-Agent: Mozilla/5.0 (Macintosh; U; Intel Mac OS X 10.6; ja-JP-mac; rv:1.9.2.3) Gecko/20100401 Firefox/3.6.3\r\n\
+    Accept: text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8\r\n\
+    Accept-Language: ja,en-us;q=0.7,en;q=0.3\r\n\
+    Accept-Encoding: gzip,deflate\r\n\
+    Accept-Charset: Shift_JIS,utf-8;q=0.7,*;q=0.7\r\n\
+    Keep-Alive: 115\r\n\
+    Connection: keep-alive\r\n\
+    Cookie: wp_ozh_wsa_visits=2; wp_ozh_wsa_visit_lasttime=xxxxxxxxxx\r\n\
+    \r\n";
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    group.throughput(Throughput::Bytes(RESPONSE.len() as u64));
+    group.bench_function("response", |b| {
+        b.iter(|| parse_response(black_box(RESPONSE)).unwrap())
+    });
+    group.bench_function("response_raw", |b| {
+        b.iter(|| {
+            let mut headers = [EMPTY_HEADER; 32];
+            black_box(parse_response_raw(black_box(RESPONSE), &mut headers).unwrap());
+        })
+    });
+
+    group.throughput(Throughput::Bytes(REQUEST.len() as u64));
+    group.bench_function("request", |b| {
+        b.iter(|| parse_request(black_box(REQUEST)).unwrap())
+    });
+    group.bench_function("request_slices", |b| {
+        b.iter(|| parse_request_slices(black_box(REQUEST)).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
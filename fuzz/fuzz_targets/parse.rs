@@ -0,0 +1,19 @@
+//! Feeds arbitrary bytes to every parser; none of them may panic.
+
+#![no_main]
+
+use http_parser::{
+    parse_request, parse_request_raw, parse_requests, parse_response, parse_response_raw,
+    EMPTY_HEADER,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_request(data);
+    let _ = parse_response(data);
+    let _ = parse_requests(data);
+    let mut headers = [EMPTY_HEADER; 16];
+    let _ = parse_request_raw(data, &mut headers);
+    let mut headers = [EMPTY_HEADER; 16];
+    let _ = parse_response_raw(data, &mut headers);
+});
@@ -0,0 +1,33 @@
+//! Starts from a valid message and overwrites bytes at fuzzer-chosen
+//! offsets, which reaches deeper parser states than random input does.
+//!
+//! The first input byte picks the message; each following pair of bytes is
+//! an offset and the value to write there.
+
+#![no_main]
+
+use http_parser::{parse_request, parse_response};
+use libfuzzer_sys::fuzz_target;
+
+const MESSAGES: &[&[u8]] = &[
+    b"GET /index?a=1 HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\n\r\n",
+    b"POST /submit HTTP/1.1\r\nHost: x\r\nContent-Length: 4\r\n\r\nbody",
+    b"GET / HTTP/1.1\nHost: x\nX-Folded: a\n b\n\n",
+    b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 2\r\n\r\nok",
+    b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n",
+    b"HTTP/1.0 404 Not Found\r\n\r\n",
+];
+
+fuzz_target!(|data: &[u8]| {
+    let (choice, edits) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let mut message = MESSAGES[usize::from(*choice) % MESSAGES.len()].to_vec();
+    for edit in edits.chunks_exact(2) {
+        let offset = usize::from(edit[0]) % message.len();
+        message[offset] = edit[1];
+    }
+    let _ = parse_request(&message);
+    let _ = parse_response(&message);
+});
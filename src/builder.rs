@@ -0,0 +1,321 @@
+use core::fmt;
+
+use crate::{is_token, HeaderSink, Request, CR, LF, SP};
+
+/// A part of the request that `RequestBuilder::build` refuses to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// A method that is not a token.
+    InvalidMethod,
+    /// A URL that is empty or contains a space, CR or LF, any of which
+    /// would let it end the request line early.
+    InvalidUrl,
+    /// An HTTP version that is empty or contains a space, CR or LF.
+    InvalidVersion,
+    /// A header name that is not a token, e.g. one containing a space or
+    /// a line break.
+    InvalidHeaderName,
+    /// A header value containing CR or LF, which would end the header line
+    /// early and let the rest be read as further headers.
+    InvalidHeaderValue,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::InvalidMethod => write!(f, "invalid method"),
+            BuildError::InvalidUrl => write!(f, "invalid url"),
+            BuildError::InvalidVersion => write!(f, "invalid http version"),
+            BuildError::InvalidHeaderName => write!(f, "invalid header name"),
+            BuildError::InvalidHeaderValue => write!(f, "invalid header value"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Serializes a request back to bytes.
+///
+/// Headers are written in insertion order with CRLF line endings. A
+/// `Content-Length` header is added when a body is set and neither
+/// `Content-Length` nor `Transfer-Encoding` was given.
+#[derive(Debug, Clone)]
+pub struct RequestBuilder {
+    method: Vec<u8>,
+    url: Vec<u8>,
+    http_version: Vec<u8>,
+    headers: Vec<(Vec<u8>, Vec<u8>)>,
+    body: Vec<u8>,
+}
+
+impl Default for RequestBuilder {
+    fn default() -> RequestBuilder {
+        RequestBuilder {
+            method: b"GET".to_vec(),
+            url: b"/".to_vec(),
+            http_version: b"HTTP/1.1".to_vec(),
+            headers: vec![],
+            body: vec![],
+        }
+    }
+}
+
+impl RequestBuilder {
+    pub fn new() -> RequestBuilder {
+        RequestBuilder::default()
+    }
+
+    pub fn method<T: AsRef<[u8]>>(&mut self, method: T) -> &mut RequestBuilder {
+        self.method = method.as_ref().to_vec();
+        self
+    }
+
+    pub fn url<T: AsRef<[u8]>>(&mut self, url: T) -> &mut RequestBuilder {
+        self.url = url.as_ref().to_vec();
+        self
+    }
+
+    pub fn http_version<T: AsRef<[u8]>>(&mut self, http_version: T) -> &mut RequestBuilder {
+        self.http_version = http_version.as_ref().to_vec();
+        self
+    }
+
+    /// Sets a header, replacing the value of an existing header with the
+    /// same name (ignoring ASCII case) or appending it otherwise. Use
+    /// `append` to keep the existing one as well.
+    pub fn header<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> &mut RequestBuilder {
+        let (key, value) = (key.as_ref(), value.as_ref());
+        match self
+            .headers
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        {
+            Some((_, existing)) => *existing = value.to_vec(),
+            None => self.headers.push((key.to_vec(), value.to_vec())),
+        }
+        self
+    }
+
+    /// Adds a header after any existing ones, even if one with the same
+    /// name is already set, e.g. for a second `Set-Cookie` or `Via`.
+    pub fn append<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> &mut RequestBuilder {
+        self.headers
+            .push((key.as_ref().to_vec(), value.as_ref().to_vec()));
+        self
+    }
+
+    pub fn body<T: AsRef<[u8]>>(&mut self, body: T) -> &mut RequestBuilder {
+        self.body = body.as_ref().to_vec();
+        self
+    }
+
+    /// Writes the request. Fails if the method or a header name is not a
+    /// token, the URL or version is empty or contains a space, CR or LF, or
+    /// a header value contains CR or LF, since any of these could smuggle
+    /// extra lines into the output.
+    pub fn build(&self) -> Result<Vec<u8>, BuildError> {
+        if !is_token(&self.method) {
+            return Err(BuildError::InvalidMethod);
+        }
+        if !is_request_line_part(&self.url) {
+            return Err(BuildError::InvalidUrl);
+        }
+        if !is_request_line_part(&self.http_version) {
+            return Err(BuildError::InvalidVersion);
+        }
+        for (key, value) in &self.headers {
+            if !is_token(key) {
+                return Err(BuildError::InvalidHeaderName);
+            }
+            if value.contains(&CR) || value.contains(&LF) {
+                return Err(BuildError::InvalidHeaderValue);
+            }
+        }
+        let mut out = vec![];
+        out.extend_from_slice(&self.method);
+        out.push(b' ');
+        out.extend_from_slice(&self.url);
+        out.push(b' ');
+        out.extend_from_slice(&self.http_version);
+        out.extend_from_slice(b"\r\n");
+        for (key, value) in &self.headers {
+            write_header(&mut out, key, value);
+        }
+        let framed = self.headers.iter().any(|(key, _)| {
+            key.eq_ignore_ascii_case(b"content-length")
+                || key.eq_ignore_ascii_case(b"transfer-encoding")
+        });
+        if !self.body.is_empty() && !framed {
+            write_header(
+                &mut out,
+                b"Content-Length",
+                self.body.len().to_string().as_bytes(),
+            );
+        }
+        out.extend_from_slice(b"\r\n");
+        out.extend_from_slice(&self.body);
+        Ok(out)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> From<&Request<'a, H>> for RequestBuilder {
+    /// Starts from a parsed request, keeping its headers in wire order.
+    fn from(request: &Request<'a, H>) -> RequestBuilder {
+        RequestBuilder {
+            method: request.method.to_vec(),
+            url: request.url.to_vec(),
+            http_version: request.http_version.to_vec(),
+            headers: request
+                .headers_ordered()
+                .map(|(key, value)| (key.to_vec(), value.to_vec()))
+                .collect(),
+            body: request.body.to_vec(),
+        }
+    }
+}
+
+/// Whether `part` can sit between the spaces of a request line.
+fn is_request_line_part(part: &[u8]) -> bool {
+    !part.is_empty() && !part.iter().any(|b| [SP, CR, LF].contains(b))
+}
+
+fn write_header(out: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+    out.extend_from_slice(key);
+    out.extend_from_slice(b": ");
+    out.extend_from_slice(value);
+    out.extend_from_slice(b"\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_request;
+
+    #[test]
+    fn test_build() {
+        let request = RequestBuilder::new()
+            .method("POST")
+            .url("/submit")
+            .header("Host", "example.com")
+            .header("Accept", "*/*")
+            .body("abc")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request,
+            b"POST /submit HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\nContent-Length: 3\r\n\r\nabc"
+        );
+        assert_eq!(
+            RequestBuilder::new().build().unwrap(),
+            b"GET / HTTP/1.1\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = b"GET /index HTTP/1.1\r\nHost: a.com\r\nAccept: */*\r\n\r\n";
+        let request = parse_request(input).unwrap();
+        let output = RequestBuilder::from(&request)
+            .header("host", "b.com")
+            .build()
+            .unwrap();
+        assert_eq!(
+            output,
+            b"GET /index HTTP/1.1\r\nHost: b.com\r\nAccept: */*\r\n\r\n"
+        );
+
+        let input = b"GET / HTTP/1.1\r\nX-A: 1\r\n\t2\r\n\r\n";
+        let request = parse_request(input).unwrap();
+        assert_eq!(
+            RequestBuilder::from(&request).build().unwrap(),
+            b"GET / HTTP/1.1\r\nX-A: 1 2\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_append() {
+        let request = RequestBuilder::new()
+            .header("Via", "1.0 a")
+            .append("via", "1.1 b")
+            .header("Accept", "*/*")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request,
+            b"GET / HTTP/1.1\r\nVia: 1.0 a\r\nvia: 1.1 b\r\nAccept: */*\r\n\r\n"
+        );
+        let request = RequestBuilder::new()
+            .append("A", "1")
+            .append("a", "2")
+            .header("A", "3")
+            .build()
+            .unwrap();
+        assert_eq!(request, b"GET / HTTP/1.1\r\nA: 3\r\na: 2\r\n\r\n");
+    }
+
+    #[test]
+    fn test_build_rejects_injection() {
+        let build = |key: &str, value: &str| RequestBuilder::new().header(key, value).build();
+        assert_eq!(
+            build("X-A", "1\r\nX-Injected: 1"),
+            Err(BuildError::InvalidHeaderValue)
+        );
+        assert_eq!(build("X-A", "1\n"), Err(BuildError::InvalidHeaderValue));
+        assert_eq!(build("X-A", "1\r"), Err(BuildError::InvalidHeaderValue));
+        assert_eq!(
+            build("X-A\r\nX-Injected", "1"),
+            Err(BuildError::InvalidHeaderName)
+        );
+        assert_eq!(build("X A", "1"), Err(BuildError::InvalidHeaderName));
+        assert_eq!(build("", "1"), Err(BuildError::InvalidHeaderName));
+        assert!(build("X-A", "a\tb").is_ok());
+    }
+
+    #[test]
+    fn test_build_rejects_request_line_injection() {
+        assert_eq!(
+            RequestBuilder::new()
+                .url("/ HTTP/1.1\r\nX-Injected: 1\r\n\r\nGET /x")
+                .build(),
+            Err(BuildError::InvalidUrl)
+        );
+        for url in ["", "/a b", "/a\r", "/a\n"] {
+            assert_eq!(
+                RequestBuilder::new().url(url).build(),
+                Err(BuildError::InvalidUrl),
+                "{:?}",
+                url
+            );
+        }
+        for method in ["", "GET /", "GET\r\n", "G\nET"] {
+            assert_eq!(
+                RequestBuilder::new().method(method).build(),
+                Err(BuildError::InvalidMethod),
+                "{:?}",
+                method
+            );
+        }
+        for version in ["", "HTTP/1.1 ", "HTTP/1.1\r\nX: 1", "HTTP/1.1\n"] {
+            assert_eq!(
+                RequestBuilder::new().http_version(version).build(),
+                Err(BuildError::InvalidVersion),
+                "{:?}",
+                version
+            );
+        }
+        assert!(RequestBuilder::new()
+            .method("PURGE")
+            .url("/a?b=c")
+            .http_version("HTTP/1.0")
+            .build()
+            .is_ok());
+    }
+}
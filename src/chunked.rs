@@ -0,0 +1,142 @@
+use crate::{parse_headers, ParserConfig};
+#[cfg(feature = "std")]
+use crate::{HeaderSink, Headers};
+
+/// Reassembles the payload of a `Transfer-Encoding: chunked` body.
+///
+/// Chunk extensions after a `;` are ignored and decoding stops at the
+/// zero-length chunk. A truncated final chunk keeps whatever bytes are left.
+#[cfg(feature = "std")]
+pub(crate) fn decode(body: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::new();
+    walk(body, &mut |chunk| decoded.extend_from_slice(chunk));
+    decoded
+}
+
+/// Parses the trailer fields after the zero-length chunk. They are empty
+/// when the body never reaches that chunk or the trailers do not parse.
+#[cfg(feature = "std")]
+pub(crate) fn trailers(body: &[u8]) -> Headers<'_> {
+    let rest = match walk(body, &mut |_| {}) {
+        Some(rest) => rest,
+        None => return Headers::default(),
+    };
+    let mut trailers = Headers::default();
+    let parsed = parse_headers(
+        rest,
+        0,
+        &ParserConfig::default(),
+        true,
+        &mut |key, value| {
+            trailers.insert(key, value);
+            Ok(())
+        },
+    );
+    match parsed {
+        Ok(_) => trailers,
+        Err(_) => Headers::default(),
+    }
+}
+
+/// Returns the length of the chunked body at the start of `buf`: every
+/// chunk, the zero-length chunk and the trailer section up to its closing
+/// blank line. `None` means the body is not complete yet, or has a
+/// malformed chunk size and never will be.
+pub(crate) fn body_len(buf: &[u8]) -> Option<usize> {
+    let rest = walk(buf, &mut |_| {})?;
+    let (_, end) =
+        parse_headers(rest, 0, &ParserConfig::default(), false, &mut |_, _| Ok(())).ok()?;
+    Some(buf.len() - rest.len() + end)
+}
+
+/// Whether `buf` holds a whole chunked body: every chunk, the zero-length
+/// chunk and the trailer section up to its closing blank line. Use it to
+/// decide whether to wait for more bytes; it does not allocate. A body
+/// with a malformed chunk size never completes.
+pub fn is_chunked_complete(buf: &[u8]) -> bool {
+    body_len(buf).is_some()
+}
+
+/// Passes the data of each chunk to `on_chunk` and returns what follows
+/// the zero-length chunk's line, or `None` if the body ends before it or
+/// a chunk's data is not followed by a line break.
+fn walk<'a>(mut body: &'a [u8], on_chunk: &mut dyn FnMut(&'a [u8])) -> Option<&'a [u8]> {
+    while let Some(line_end) = body.iter().position(|b| *b == b'\n') {
+        let size = chunk_size(&body[..line_end])?;
+        body = &body[line_end + 1..];
+        if size == 0 {
+            return Some(body);
+        }
+        match body.get(..size) {
+            Some(chunk) => on_chunk(chunk),
+            None => {
+                on_chunk(body);
+                return None;
+            }
+        }
+        body = &body[size..];
+        if body.starts_with(b"\r\n") {
+            body = &body[2..];
+        } else if body.starts_with(b"\n") {
+            body = &body[1..];
+        } else {
+            return None;
+        }
+    }
+    None
+}
+
+pub(crate) fn chunk_size(line: &[u8]) -> Option<usize> {
+    let size = line.split(|b| *b == b';').next().unwrap_or(line);
+    let size = match size.iter().rposition(|b| !b" \t\r".contains(b)) {
+        Some(end) => &size[..=end],
+        None => return None,
+    };
+    size.iter().try_fold(0usize, |acc, b| {
+        let digit = (*b as char).to_digit(16)? as usize;
+        acc.checked_mul(16)?.checked_add(digit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_decode() {
+        assert_eq!(
+            decode(b"4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n"),
+            b"Wikipedia"
+        );
+        assert_eq!(decode(b"3;name=value\r\nabc\r\n0\r\n\r\n"), b"abc");
+        assert_eq!(decode(b"a\nabcdefghij\n0\n\n"), b"abcdefghij");
+        assert_eq!(decode(b"0\r\n\r\n"), b"");
+    }
+
+    #[test]
+    fn test_is_chunked_complete() {
+        let body = b"4\r\nWiki\r\n5\r\npedia\r\n0\r\nX-A: 1\r\n\r\n";
+        assert!(is_chunked_complete(body));
+        for end in 0..body.len() {
+            assert!(!is_chunked_complete(&body[..end]), "{}", end);
+        }
+        assert!(is_chunked_complete(b"0\r\n\r\n"));
+        assert!(is_chunked_complete(b"3;ext=1\nabc\n0\n\n"));
+        assert!(!is_chunked_complete(b"3\r\n0\r\n\r\n"));
+        assert!(!is_chunked_complete(b"zz\r\nabc\r\n0\r\n\r\n"));
+        // Chunk data must be followed by its line break.
+        assert!(!is_chunked_complete(b"3\r\nabc0\r\n\r\n"));
+        assert!(!is_chunked_complete(b"3\r\nabcd\r\n0\r\n\r\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_trailers() {
+        let fields = trailers(b"4\r\nWiki\r\n0\r\nX-Checksum: abc\r\nX-Other: 1\r\n\r\n");
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields.get("x-checksum"), Some(&b"abc"[..]));
+        assert!(trailers(b"0\r\n\r\n").is_empty());
+        assert!(trailers(b"4\r\nWi").is_empty());
+    }
+}
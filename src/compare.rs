@@ -0,0 +1,60 @@
+use crate::{HeaderSink, Request, Response};
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Whether the method is exactly `method`. Methods are case-sensitive.
+    pub fn method_eq(&self, method: &str) -> bool {
+        self.method == method.as_bytes()
+    }
+
+    /// Whether the URL is exactly `url`.
+    pub fn url_eq(&self, url: &str) -> bool {
+        self.url == url.as_bytes()
+    }
+
+    /// Whether the version is exactly `http_version`, e.g. `"HTTP/1.1"`.
+    pub fn http_version_eq(&self, http_version: &str) -> bool {
+        self.http_version == http_version.as_bytes()
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Whether the status code is exactly `status_code`, e.g. `"200"`.
+    pub fn status_code_eq(&self, status_code: &str) -> bool {
+        self.status_code == status_code.as_bytes()
+    }
+
+    /// Whether the reason phrase is exactly `status`.
+    pub fn status_eq(&self, status: &str) -> bool {
+        self.status == status.as_bytes()
+    }
+
+    /// Whether the version is exactly `http_version`, e.g. `"HTTP/1.1"`.
+    pub fn http_version_eq(&self, http_version: &str) -> bool {
+        self.http_version == http_version.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_request_eq() {
+        let result = parse_request(b"GET /index HTTP/1.1\r\n\r\n").unwrap();
+        assert!(result.method_eq("GET"));
+        assert!(!result.method_eq("get"));
+        assert!(result.url_eq("/index"));
+        assert!(!result.url_eq("/"));
+        assert!(result.http_version_eq("HTTP/1.1"));
+        assert_eq!(result.method, b"GET");
+    }
+
+    #[test]
+    fn test_response_eq() {
+        let result = parse_response(b"HTTP/1.0 404 Not Found\r\n\r\n").unwrap();
+        assert!(result.status_code_eq("404"));
+        assert!(result.status_eq("Not Found"));
+        assert!(result.http_version_eq("HTTP/1.0"));
+        assert!(!result.http_version_eq("HTTP/1.1"));
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::{trim_whitespace, HeaderSink, Request, Response};
+
+/// A cookie set by a `Set-Cookie` response header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cookie<'a> {
+    pub name: &'a [u8],
+    pub value: &'a [u8],
+    pub path: Option<&'a [u8]>,
+    pub domain: Option<&'a [u8]>,
+    /// The `Max-Age` attribute in seconds; zero or negative expires the cookie.
+    pub max_age: Option<i64>,
+    pub secure: bool,
+    pub http_only: bool,
+}
+
+impl<'a> Cookie<'a> {
+    /// Parses a `Set-Cookie` value. Attributes other than `Path`, `Domain`,
+    /// `Max-Age`, `Secure` and `HttpOnly` are ignored.
+    pub fn parse(value: &'a [u8]) -> Cookie<'a> {
+        let mut parts = value.split(|b| *b == b';');
+        let (name, value) = split_pair(parts.next().unwrap_or_default());
+        let mut cookie = Cookie {
+            name,
+            value,
+            ..Cookie::default()
+        };
+        for attribute in parts {
+            let (name, value) = split_pair(attribute);
+            if name.eq_ignore_ascii_case(b"path") {
+                cookie.path = Some(value);
+            } else if name.eq_ignore_ascii_case(b"domain") {
+                cookie.domain = Some(value);
+            } else if name.eq_ignore_ascii_case(b"max-age") {
+                cookie.max_age = std::str::from_utf8(value).ok().and_then(|v| v.parse().ok());
+            } else if name.eq_ignore_ascii_case(b"secure") {
+                cookie.secure = true;
+            } else if name.eq_ignore_ascii_case(b"httponly") {
+                cookie.http_only = true;
+            }
+        }
+        cookie
+    }
+}
+
+/// Splits `name=value`, trimming whitespace around both. Without `=` the
+/// whole string is the name and the value is empty.
+fn split_pair(pair: &[u8]) -> (&[u8], &[u8]) {
+    match pair.iter().position(|b| *b == b'=') {
+        Some(i) => (trim_whitespace(&pair[..i]), trim_whitespace(&pair[i + 1..])),
+        None => (trim_whitespace(pair), &[]),
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Parses the `Cookie` headers into name/value pairs.
+    pub fn cookies(&self) -> HashMap<&[u8], &[u8]> {
+        self.header_all("cookie")
+            .into_iter()
+            .flat_map(|header| header.split(|b| *b == b';'))
+            .map(split_pair)
+            .filter(|(name, _)| !name.is_empty())
+            .collect()
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Parses every `Set-Cookie` header, in the order they appeared.
+    pub fn set_cookies(&self) -> Vec<Cookie<'_>> {
+        self.header_all("set-cookie")
+            .into_iter()
+            .map(Cookie::parse)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_cookies() {
+        let input = b"GET / HTTP/1.1\r\nCookie: a=1; b = 2 ;flag\r\nCookie: c=3\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        let cookies = result.cookies();
+        assert_eq!(cookies.len(), 4);
+        assert_eq!(cookies.get(&b"a"[..]), Some(&&b"1"[..]));
+        assert_eq!(cookies.get(&b"b"[..]), Some(&&b"2"[..]));
+        assert_eq!(cookies.get(&b"flag"[..]), Some(&&b""[..]));
+        assert_eq!(cookies.get(&b"c"[..]), Some(&&b"3"[..]));
+    }
+
+    #[test]
+    fn test_set_cookies() {
+        let input = b"HTTP/1.1 200 OK\r\n\
+            Set-Cookie: id=a3fWa; Path=/docs; Domain=example.com; Max-Age=3600; Secure; HttpOnly\r\n\
+            Set-Cookie: lang=en; SameSite=Lax\r\n\
+            Set-Cookie: Secure\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        let cookies = result.set_cookies();
+        assert_eq!(
+            cookies[0],
+            Cookie {
+                name: b"id",
+                value: b"a3fWa",
+                path: Some(b"/docs"),
+                domain: Some(b"example.com"),
+                max_age: Some(3600),
+                secure: true,
+                http_only: true,
+            }
+        );
+        assert_eq!(cookies[1].name, b"lang");
+        assert_eq!(cookies[1].path, None);
+        assert!(!cookies[1].secure);
+        assert_eq!(cookies[2].name, b"Secure");
+        assert_eq!(cookies[2].value, b"");
+    }
+}
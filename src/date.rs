@@ -0,0 +1,168 @@
+use crate::{HeaderSink, Request, Response};
+
+/// A point in time from a date header, in UTC. Dates compare in
+/// chronological order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub min: u8,
+    pub sec: u8,
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl HttpDate {
+    /// Parses a date in any of the formats allowed by RFC 7231 §7.1.1.1:
+    /// `Sun, 06 Nov 1994 08:49:37 GMT`, the obsolete
+    /// `Sunday, 06-Nov-94 08:49:37 GMT` and asctime's
+    /// `Sun Nov  6 08:49:37 1994`. The weekday is not checked.
+    pub fn parse(value: &[u8]) -> Option<HttpDate> {
+        let value = core::str::from_utf8(value).ok()?;
+        let parts: Vec<&str> = value.split_ascii_whitespace().collect();
+        let (year, month, day, time) = match parts[..] {
+            [weekday, day, month, year, time, "GMT"] if weekday.ends_with(',') => {
+                (number(year, 4, 4)?, month, number(day, 2, 2)?, time)
+            }
+            [weekday, date, time, "GMT"] if weekday.ends_with(',') => {
+                let mut date = date.split('-');
+                let day = number(date.next()?, 2, 2)?;
+                let month = date.next()?;
+                // Two-digit years are read as 1970-2069.
+                let year = match number(date.next()?, 2, 2)? {
+                    year if year < 70 => 2000 + year,
+                    year => 1900 + year,
+                };
+                if date.next().is_some() {
+                    return None;
+                }
+                (year, month, day, time)
+            }
+            [_, month, day, time, year] => (number(year, 4, 4)?, month, number(day, 1, 2)?, time),
+            _ => return None,
+        };
+        let month = MONTHS.iter().position(|name| *name == month)? as u8 + 1;
+        let mut time = time.split(':');
+        let mut field = || number(time.next()?, 2, 2);
+        let (hour, min, sec) = (field()?, field()?, field()?);
+        if time.next().is_some() {
+            return None;
+        }
+        // A second of 60 allows for leap seconds.
+        if day == 0 || day > days_in_month(year, month) || hour > 23 || min > 59 || sec > 60 {
+            return None;
+        }
+        Some(HttpDate {
+            year,
+            month,
+            day: day as u8,
+            hour: hour as u8,
+            min: min as u8,
+            sec: sec as u8,
+        })
+    }
+}
+
+/// Reads a decimal number of `min..=max` digits.
+fn number(digits: &str, min: usize, max: usize) -> Option<u16> {
+    if digits.len() < min || digits.len() > max || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn days_in_month(year: u16, month: u8) -> u16 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Parses the `If-Modified-Since` header.
+    pub fn if_modified_since(&self) -> Option<HttpDate> {
+        self.header("if-modified-since").and_then(HttpDate::parse)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Parses the `Date` header.
+    pub fn date(&self) -> Option<HttpDate> {
+        self.header("date").and_then(HttpDate::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request, parse_response};
+
+    const EXPECTED: HttpDate = HttpDate {
+        year: 1994,
+        month: 11,
+        day: 6,
+        hour: 8,
+        min: 49,
+        sec: 37,
+    };
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(
+            HttpDate::parse(b"Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(EXPECTED)
+        );
+        assert_eq!(
+            HttpDate::parse(b"Sunday, 06-Nov-94 08:49:37 GMT"),
+            Some(EXPECTED)
+        );
+        assert_eq!(HttpDate::parse(b"Sun Nov  6 08:49:37 1994"), Some(EXPECTED));
+        assert_eq!(
+            HttpDate::parse(b"Thursday, 01-Jan-15 00:00:00 GMT").map(|date| date.year),
+            Some(2015)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for value in [
+            &b""[..],
+            b"yesterday",
+            b"Sun, 06 Nov 1994 08:49:37 UTC",
+            b"Sun, 06 Foo 1994 08:49:37 GMT",
+            b"Sun, 31 Nov 1994 08:49:37 GMT",
+            b"Sun, 29 Feb 1900 00:00:00 GMT",
+            b"Sun, 06 Nov 1994 24:00:00 GMT",
+            b"Sun, 06 Nov 1994 08:49 GMT",
+            b"Sun, 6 Nov 1994 08:49:37 GMT",
+            b"Sunday, 06-Nov-94-1 08:49:37 GMT",
+        ] {
+            assert_eq!(HttpDate::parse(value), None);
+        }
+        assert!(HttpDate::parse(b"Tue, 29 Feb 2000 00:00:00 GMT").is_some());
+    }
+
+    #[test]
+    fn test_headers() {
+        let input = b"HTTP/1.1 200 OK\r\nDate: Sun, 06 Nov 1994 08:49:37 GMT\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.date(), Some(EXPECTED));
+        assert_eq!(
+            parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap().date(),
+            None
+        );
+
+        let input = b"GET / HTTP/1.1\r\nIf-Modified-Since: Sun Nov  6 08:49:37 1994\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.if_modified_since(), Some(EXPECTED));
+        assert!(HttpDate::parse(b"Mon, 07 Nov 1994 00:00:00 GMT").unwrap() > EXPECTED);
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+
+use crate::{HeaderSink, Headers, Request, Response};
+
+/// Formats bytes as a lossy UTF-8 string.
+struct Lossy<'a>(&'a [u8]);
+
+impl fmt::Debug for Lossy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&String::from_utf8_lossy(self.0), f)
+    }
+}
+
+impl fmt::Display for Lossy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&String::from_utf8_lossy(self.0), f)
+    }
+}
+
+impl fmt::Debug for Headers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(key, value)| (Lossy(key), Lossy(value))))
+            .finish()
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> fmt::Debug for Request<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("method", &Lossy(self.method))
+            .field("url", &Lossy(self.url))
+            .field("http_version", &Lossy(self.http_version))
+            .field("headers", self.fields())
+            .field("body", &Lossy(self.body))
+            .finish()
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> fmt::Debug for Response<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Response")
+            .field("http_version", &Lossy(self.http_version))
+            .field("status_code", &Lossy(self.status_code))
+            .field("status", &Lossy(self.status))
+            .field("headers", self.fields())
+            .field("body", &Lossy(self.body))
+            .finish()
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Returns the request line as bytes, e.g. `GET /index HTTP/1.1`, with
+    /// its parts joined by single spaces. Unlike `Display`, invalid UTF-8
+    /// is kept as it is.
+    pub fn request_line(&self) -> Vec<u8> {
+        [self.method, self.url, self.http_version].join(&b' ')
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Returns the status line as bytes, e.g. `HTTP/1.1 200 OK`, with its
+    /// parts joined by single spaces. An empty reason phrase is left out,
+    /// as in `Display`.
+    pub fn status_line(&self) -> Vec<u8> {
+        let mut line = [self.http_version, self.status_code].join(&b' ');
+        if !self.status.is_empty() {
+            line.push(b' ');
+            line.extend_from_slice(self.status);
+        }
+        line
+    }
+}
+
+/// Writes the request line, e.g. `GET /index HTTP/1.1`.
+impl<'a, H: HeaderSink<'a>> fmt::Display for Request<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            Lossy(self.method),
+            Lossy(self.url),
+            Lossy(self.http_version)
+        )
+    }
+}
+
+/// Writes the status line, e.g. `HTTP/1.1 200 OK`.
+impl<'a, H: HeaderSink<'a>> fmt::Display for Response<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            Lossy(self.http_version),
+            Lossy(self.status_code)
+        )?;
+        if !self.status.is_empty() {
+            write!(f, " {}", Lossy(self.status))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_debug() {
+        let input = b"GET /index HTTP/1.1\r\nHost: x\r\nAccept: */*\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(
+            format!("{:?}", result),
+            r#"Request { method: "GET", url: "/index", http_version: "HTTP/1.1", headers: {"Host": "x", "Accept": "*/*"}, body: "" }"#
+        );
+
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n\xffa";
+        let result = parse_response(input).unwrap();
+        assert_eq!(
+            format!("{:?}", result),
+            "Response { http_version: \"HTTP/1.1\", status_code: \"200\", status: \"OK\", \
+             headers: {\"Content-Length\": \"2\"}, body: \"\u{fffd}a\" }"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let result = parse_request(b"GET /index HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.to_string(), "GET /index HTTP/1.1");
+        let result = parse_response(b"HTTP/1.1 404 Not Found\r\n\r\n").unwrap();
+        assert_eq!(result.to_string(), "HTTP/1.1 404 Not Found");
+        let result = parse_response(b"HTTP/1.0 200\r\n\r\n").unwrap();
+        assert_eq!(result.to_string(), "HTTP/1.0 200");
+    }
+
+    #[test]
+    fn test_start_lines() {
+        let result = parse_request(b"\r\nGET /index?q=\xff HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.request_line(), b"GET /index?q=\xff HTTP/1.1");
+
+        let result = parse_response(b"HTTP/1.1 404 Not Found \r\n\r\n").unwrap();
+        assert_eq!(result.status_line(), b"HTTP/1.1 404 Not Found");
+        let result = parse_response(b"HTTP/1.0 204\r\n\r\n").unwrap();
+        assert_eq!(result.status_line(), b"HTTP/1.0 204");
+        assert_eq!(result.status_line(), result.to_string().as_bytes());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::media_type::{parameters, quality};
+use crate::{trim_whitespace, HeaderSink, Request};
+
+/// A content-coding named in `Accept-Encoding`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Gzip,
+    Deflate,
+    Br,
+    Identity,
+    /// The `*` wildcard, matching any coding not listed otherwise.
+    Any,
+    /// Any other coding, with the raw bytes preserved.
+    Other(Vec<u8>),
+}
+
+impl From<&[u8]> for Encoding {
+    /// Maps a content-coding token to its variant, ignoring ASCII case.
+    /// `x-gzip` is an alias of `gzip` (RFC 7230 §4.2.3).
+    fn from(coding: &[u8]) -> Encoding {
+        let is = |name: &[u8]| coding.eq_ignore_ascii_case(name);
+        if is(b"gzip") || is(b"x-gzip") {
+            Encoding::Gzip
+        } else if is(b"deflate") {
+            Encoding::Deflate
+        } else if is(b"br") {
+            Encoding::Br
+        } else if is(b"identity") {
+            Encoding::Identity
+        } else if coding == b"*" {
+            Encoding::Any
+        } else {
+            Encoding::Other(coding.to_vec())
+        }
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Parses the `Accept-Encoding` headers into codings with their `q`
+    /// values, most preferred first. Codings with equal quality keep the
+    /// order they were listed in. A quality of `0`, as in `identity;q=0`,
+    /// marks the coding as not acceptable.
+    pub fn accept_encoding(&self) -> Vec<(Encoding, f32)> {
+        let mut codings: Vec<_> = self
+            .header_all("accept-encoding")
+            .into_iter()
+            .flat_map(|value| value.split(|b| *b == b','))
+            .filter_map(|coding| {
+                let mut parts = coding.split(|b| *b == b';');
+                let name = trim_whitespace(parts.next()?);
+                if name.is_empty() {
+                    return None;
+                }
+                let quality = parameters(parts)
+                    .find(|(name, _)| name.eq_ignore_ascii_case(b"q"))
+                    .and_then(|(_, value)| quality(value))
+                    .unwrap_or(1.0);
+                Some((Encoding::from(name), quality))
+            })
+            .collect();
+        codings.sort_by(|a, b| b.1.total_cmp(&a.1));
+        codings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_request;
+
+    #[test]
+    fn test_accept_encoding() {
+        let input = b"GET / HTTP/1.1\r\nAccept-Encoding: deflate;q=0.5, GZIP, identity;q=0\r\n\
+            Accept-Encoding: br;q=0.8, *;q=0.1, zstd\r\n\r\n";
+        assert_eq!(
+            parse_request(input).unwrap().accept_encoding(),
+            [
+                (Encoding::Gzip, 1.0),
+                (Encoding::Other(b"zstd".to_vec()), 1.0),
+                (Encoding::Br, 0.8),
+                (Encoding::Deflate, 0.5),
+                (Encoding::Any, 0.1),
+                (Encoding::Identity, 0.0),
+            ]
+        );
+
+        let input = b"GET / HTTP/1.1\r\nAccept-Encoding: x-gzip;q=bad, ,\r\n\r\n";
+        assert_eq!(
+            parse_request(input).unwrap().accept_encoding(),
+            [(Encoding::Gzip, 1.0)]
+        );
+        let result = parse_request(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert!(result.accept_encoding().is_empty());
+    }
+}
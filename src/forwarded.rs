@@ -0,0 +1,69 @@
+use crate::{trim_whitespace, HeaderSink, Request};
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Collects the client addresses a request passed through proxies for:
+    /// the `for=` parameters of `Forwarded` (RFC 7239) followed by the
+    /// entries of `X-Forwarded-For`, each in the order they appeared.
+    /// Quoted values are returned without their quotes.
+    pub fn forwarded_for(&self) -> Vec<&[u8]> {
+        let forwarded = self
+            .header_all("forwarded")
+            .into_iter()
+            .flat_map(|value| value.split(|b| *b == b','))
+            .flat_map(|element| element.split(|b| *b == b';'))
+            .filter_map(|pair| {
+                let eq = pair.iter().position(|b| *b == b'=')?;
+                if !trim_whitespace(&pair[..eq]).eq_ignore_ascii_case(b"for") {
+                    return None;
+                }
+                Some(unquote(trim_whitespace(&pair[eq + 1..])))
+            });
+        let x_forwarded_for = self
+            .header_all("x-forwarded-for")
+            .into_iter()
+            .flat_map(|value| value.split(|b| *b == b','))
+            .map(|address| unquote(trim_whitespace(address)));
+        forwarded
+            .chain(x_forwarded_for)
+            .filter(|address| !address.is_empty())
+            .collect()
+    }
+}
+
+fn unquote(value: &[u8]) -> &[u8] {
+    match value {
+        [b'"', inner @ .., b'"'] => inner,
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_request;
+
+    #[test]
+    fn test_forwarded_for() {
+        let input = b"GET / HTTP/1.1\r\nX-Forwarded-For: 203.0.113.1, 198.51.100.2\r\n\
+            x-forwarded-for:10.0.0.1\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(
+            result.forwarded_for(),
+            [&b"203.0.113.1"[..], b"198.51.100.2", b"10.0.0.1"]
+        );
+
+        let input = b"GET / HTTP/1.1\r\nForwarded: For=\"[2001:db8::1]:4711\";proto=http, \
+            for = 192.0.2.43;by=10.0.0.1\r\nX-Forwarded-For: 10.0.0.2\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(
+            result.forwarded_for(),
+            [&b"[2001:db8::1]:4711"[..], b"192.0.2.43", b"10.0.0.2"]
+        );
+
+        let input = b"GET / HTTP/1.1\r\nX-Forwarded-For: 10.0.0.1,\r\n 10.0.0.2\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.forwarded_for(), [&b"10.0.0.1"[..], b"10.0.0.2"]);
+
+        let input = b"GET / HTTP/1.1\r\nForwarded: proto=https\r\nX-Forwarded-For: ,\r\n\r\n";
+        assert!(parse_request(input).unwrap().forwarded_for().is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{find_header, HeaderSink, Request, Response};
+
+/// A header name, compared without regard to ASCII case.
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderName<'a>(&'a [u8]);
+
+impl HeaderName<'static> {
+    pub const ACCEPT: HeaderName<'static> = HeaderName(b"Accept");
+    pub const ACCEPT_ENCODING: HeaderName<'static> = HeaderName(b"Accept-Encoding");
+    pub const ACCEPT_LANGUAGE: HeaderName<'static> = HeaderName(b"Accept-Language");
+    pub const AUTHORIZATION: HeaderName<'static> = HeaderName(b"Authorization");
+    pub const CACHE_CONTROL: HeaderName<'static> = HeaderName(b"Cache-Control");
+    pub const CONNECTION: HeaderName<'static> = HeaderName(b"Connection");
+    pub const CONTENT_ENCODING: HeaderName<'static> = HeaderName(b"Content-Encoding");
+    pub const CONTENT_LENGTH: HeaderName<'static> = HeaderName(b"Content-Length");
+    pub const CONTENT_TYPE: HeaderName<'static> = HeaderName(b"Content-Type");
+    pub const COOKIE: HeaderName<'static> = HeaderName(b"Cookie");
+    pub const DATE: HeaderName<'static> = HeaderName(b"Date");
+    pub const EXPECT: HeaderName<'static> = HeaderName(b"Expect");
+    pub const HOST: HeaderName<'static> = HeaderName(b"Host");
+    pub const LOCATION: HeaderName<'static> = HeaderName(b"Location");
+    pub const RANGE: HeaderName<'static> = HeaderName(b"Range");
+    pub const SERVER: HeaderName<'static> = HeaderName(b"Server");
+    pub const SET_COOKIE: HeaderName<'static> = HeaderName(b"Set-Cookie");
+    pub const TRANSFER_ENCODING: HeaderName<'static> = HeaderName(b"Transfer-Encoding");
+    pub const UPGRADE: HeaderName<'static> = HeaderName(b"Upgrade");
+    pub const USER_AGENT: HeaderName<'static> = HeaderName(b"User-Agent");
+}
+
+impl<'a> HeaderName<'a> {
+    /// A header name not covered by the constants.
+    pub const fn from_bytes(name: &'a [u8]) -> HeaderName<'a> {
+        HeaderName(name)
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns the name title-cased per hyphen-separated segment, e.g.
+    /// `Content-Type` for `content-type`. Names whose usual spelling is not
+    /// title case, such as `ETag`, are returned in that spelling.
+    pub fn canonical(&self) -> Vec<u8> {
+        if let Some(name) = IRREGULAR
+            .iter()
+            .find(|name| self.0.eq_ignore_ascii_case(name))
+        {
+            return name.to_vec();
+        }
+        let mut segment_start = true;
+        self.0
+            .iter()
+            .map(|b| {
+                let b = if segment_start {
+                    b.to_ascii_uppercase()
+                } else {
+                    b.to_ascii_lowercase()
+                };
+                segment_start = b == b'-';
+                b
+            })
+            .collect()
+    }
+}
+
+const IRREGULAR: [&[u8]; 2] = [b"ETag", b"WWW-Authenticate"];
+
+impl PartialEq for HeaderName<'_> {
+    fn eq(&self, other: &HeaderName<'_>) -> bool {
+        self.0.eq_ignore_ascii_case(other.0)
+    }
+}
+
+impl Eq for HeaderName<'_> {}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Looks up a header by name, ignoring ASCII case.
+    pub fn get(&self, name: HeaderName<'_>) -> Option<&[u8]> {
+        find_header(self.fields().iter(), name.0)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Looks up a header by name, ignoring ASCII case.
+    pub fn get(&self, name: HeaderName<'_>) -> Option<&[u8]> {
+        find_header(self.fields().iter(), name.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_get() {
+        let input = b"GET / HTTP/1.1\r\nhost: example.com\r\nX-Trace-Id: 7\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.get(HeaderName::HOST), Some(&b"example.com"[..]));
+        assert_eq!(result.get(HeaderName::USER_AGENT), None);
+        assert_eq!(
+            result.get(HeaderName::from_bytes(b"x-trace-id")),
+            Some(&b"7"[..])
+        );
+
+        let input = b"HTTP/1.1 200 OK\r\nCONTENT-TYPE: text/plain\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(
+            result.get(HeaderName::CONTENT_TYPE),
+            Some(&b"text/plain"[..])
+        );
+    }
+
+    #[test]
+    fn test_canonical() {
+        let canonical = |name: &[u8]| HeaderName::from_bytes(name).canonical();
+        assert_eq!(canonical(b"content-type"), b"Content-Type");
+        assert_eq!(canonical(b"X-FORWARDED-FOR"), b"X-Forwarded-For");
+        assert_eq!(canonical(b"etag"), b"ETag");
+        assert_eq!(canonical(b"www-authenticate"), b"WWW-Authenticate");
+        assert_eq!(HeaderName::CONTENT_LENGTH.canonical(), b"Content-Length");
+        assert_eq!(canonical(b"ETag"), b"ETag");
+    }
+
+    #[test]
+    fn test_eq_ignores_case() {
+        assert_eq!(
+            HeaderName::from_bytes(b"content-type"),
+            HeaderName::CONTENT_TYPE
+        );
+        assert_ne!(HeaderName::HOST, HeaderName::CONTENT_TYPE);
+    }
+}
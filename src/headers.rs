@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+
+use crate::{find_header, find_headers, HeaderSink, CR, HTAB, LF, SP};
+
+/// The headers of a parsed message, in the order they appeared. Lookups
+/// ignore ASCII case in the name.
+///
+/// A value folded over several lines (obs-fold) is stored with each fold
+/// replaced by a single space, as RFC 7230 §3.2.4 asks of a recipient;
+/// only such values are copied.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Headers<'a> {
+    fields: Vec<(Cow<'a, [u8]>, Value<'a>)>,
+}
+
+/// A header value: borrowed from the input unless it had to be unfolded.
+type Value<'a> = Cow<'a, [u8]>;
+
+impl<'a> Headers<'a> {
+    /// Returns the value of the first header called `name`.
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        find_header(self.iter(), name.as_bytes())
+    }
+
+    /// Returns every value of a repeated header, in the order they appeared.
+    pub fn get_all(&self, name: &str) -> Vec<&[u8]> {
+        find_headers(self.iter(), name.as_bytes())
+    }
+
+    /// Returns whether any header is called `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Iterates over the header names and values in wire order.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> + Clone {
+        self.fields
+            .iter()
+            .map(|(key, value)| (&key[..], &value[..]))
+    }
+
+    /// Returns the number of header lines, counting repeated names.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns whether the message has no headers.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Copies every borrowed name and value, detaching the headers from
+    /// the input.
+    pub(crate) fn into_owned(self) -> Headers<'static> {
+        let own = |bytes: Cow<'a, [u8]>| Cow::Owned(bytes.into_owned());
+        Headers {
+            fields: self
+                .fields
+                .into_iter()
+                .map(|(key, value)| (own(key), own(value)))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> HeaderSink<'a> for Headers<'a> {
+    fn insert(&mut self, key: &'a [u8], value: &'a [u8]) {
+        self.fields.push((Cow::Borrowed(key), unfold(value)));
+    }
+
+    fn insert_lowercase(&mut self, key: &'a [u8], value: &'a [u8]) {
+        let key = if key.iter().any(u8::is_ascii_uppercase) {
+            Cow::Owned(key.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(key)
+        };
+        self.fields.push((key, unfold(value)));
+    }
+
+    fn ordered(&self) -> Option<&Headers<'a>> {
+        Some(self)
+    }
+}
+
+/// Replaces each obs-fold (a line break followed by spaces or tabs) in a
+/// header value with a single space.
+fn unfold(value: &[u8]) -> Cow<'_, [u8]> {
+    if !value.contains(&LF) {
+        return Cow::Borrowed(value);
+    }
+    let mut unfolded = Vec::with_capacity(value.len());
+    let mut bytes = value.iter().peekable();
+    while let Some(b) = bytes.next() {
+        match *b {
+            CR if bytes.peek() == Some(&&LF) => {}
+            LF => {
+                while bytes.next_if(|b| **b == SP || **b == HTAB).is_some() {}
+                unfolded.push(SP);
+            }
+            b => unfolded.push(b),
+        }
+    }
+    Cow::Owned(unfolded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_request, parse_request_with_config, parse_response, ParserConfig};
+
+    #[test]
+    fn test_headers() {
+        let input = b"GET / HTTP/1.1\r\nHost: x\r\nAccept: a\r\naccept: b\r\n\r\n";
+        let headers = parse_request(input).unwrap().headers;
+        assert_eq!(headers.len(), 3);
+        assert!(!headers.is_empty());
+        assert_eq!(headers.get("HOST"), Some(&b"x"[..]));
+        assert_eq!(headers.get("accept"), Some(&b"a"[..]));
+        assert_eq!(headers.get_all("Accept"), [&b"a"[..], b"b"]);
+        assert!(headers.contains("host"));
+        assert!(!headers.contains("cookie"));
+        let names: Vec<_> = headers.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, [&b"Host"[..], b"Accept", b"accept"]);
+
+        let headers = parse_response(b"HTTP/1.1 204 No Content\r\n\r\n")
+            .unwrap()
+            .headers;
+        assert!(headers.is_empty());
+        assert_eq!(headers.get("host"), None);
+    }
+
+    #[test]
+    fn test_lowercase_headers() {
+        let config = ParserConfig {
+            lowercase_headers: true,
+            ..ParserConfig::default()
+        };
+        let input = b"GET / HTTP/1.1\r\nHost: x\r\nx-id: 1\r\nContent-TYPE: a\r\n\r\n";
+        let result = parse_request_with_config(input, &config).unwrap();
+        let names: Vec<_> = result.headers.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, [&b"host"[..], b"x-id", b"content-type"]);
+        assert_eq!(result.headers.get("Content-Type"), Some(&b"a"[..]));
+        let ordered: Vec<_> = result.headers_ordered().map(|(name, _)| name).collect();
+        assert_eq!(ordered, names);
+        assert_eq!(result.header("content-type"), Some(&b"a"[..]));
+        assert!(result.raw_headers().ends_with(b"Content-TYPE: a\r\n"));
+    }
+}
@@ -1,237 +1,2803 @@
-use std::collections::HashMap;
+//! A small zero-copy HTTP/1.x request and response parser.
+//!
+//! The `std` feature is enabled by default. Without it the crate is
+//! `#![no_std]`, needs no allocator, and provides exactly these items:
+//! `parse_request_raw`, `parse_response_raw`, `RawRequest`, `RawResponse`,
+//! `EMPTY_HEADER`, `is_chunked_complete`, `ParseError` and `ParserConfig`.
+//! Every other item allocates, if only the `Headers` store or a `Vec` of
+//! results, and requires `std`.
+//!
+//! The optional `fast-scan` feature finds the end of each header name and
+//! value with `memchr` rather than byte by byte. It produces the same
+//! results and works without `std`.
+//!
+//! The optional `bytes` feature adds `BytesRequest` and `BytesResponse`,
+//! owned messages whose fields are `bytes::Bytes` slices of the parsed
+//! buffer, and `parse_request_bytes` and `parse_response_bytes` to build
+//! them.
 
-pub struct Response<'a> {
-    pub status: &'a [u8],
-    pub status_code: &'a [u8],
-    pub http_version: &'a [u8],
-    pub headers: HashMap<&'a [u8], &'a [u8]>,
-    pub body: &'a [u8],
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::str::Utf8Error;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "std")]
+mod auth;
+#[cfg(feature = "std")]
+mod builder;
+mod chunked;
+#[cfg(feature = "std")]
+mod compare;
+#[cfg(feature = "std")]
+mod cookie;
+#[cfg(feature = "std")]
+mod date;
+#[cfg(feature = "std")]
+mod display;
+#[cfg(feature = "std")]
+mod encoding;
+#[cfg(feature = "std")]
+mod forwarded;
+#[cfg(feature = "std")]
+mod header_name;
+#[cfg(feature = "std")]
+mod headers;
+#[cfg(feature = "std")]
+mod media_type;
+#[cfg(feature = "std")]
+mod method;
+#[cfg(feature = "std")]
+mod multipart;
+#[cfg(feature = "std")]
+mod owned;
+#[cfg(feature = "std")]
+mod range;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "bytes")]
+mod shared;
+#[cfg(feature = "std")]
+mod sink;
+#[cfg(feature = "std")]
+mod slices;
+#[cfg(feature = "std")]
+mod spans;
+#[cfg(feature = "std")]
+mod streaming;
+#[cfg(feature = "std")]
+mod upgrade;
+#[cfg(feature = "std")]
+mod url;
+#[cfg(feature = "std")]
+mod validate;
+#[cfg(feature = "std")]
+mod vary;
+#[cfg(feature = "std")]
+mod version;
+
+#[cfg(feature = "std")]
+pub use builder::{BuildError, RequestBuilder};
+pub use chunked::is_chunked_complete;
+#[cfg(feature = "std")]
+pub use cookie::Cookie;
+#[cfg(feature = "std")]
+pub use date::HttpDate;
+#[cfg(feature = "std")]
+pub use encoding::Encoding;
+#[cfg(feature = "std")]
+pub use header_name::HeaderName;
+#[cfg(feature = "std")]
+pub use headers::Headers;
+#[cfg(feature = "std")]
+pub use media_type::{MediaRange, MediaType};
+#[cfg(feature = "std")]
+pub use method::Method;
+#[cfg(feature = "std")]
+pub use multipart::{parse_multipart, Part};
+#[cfg(feature = "std")]
+pub use owned::{OwnedRequest, OwnedResponse};
+#[cfg(feature = "std")]
+pub use range::{ByteRange, ContentRange};
+#[cfg(feature = "std")]
+pub use reader::{parse_request_from_reader, parse_request_from_reader_with_config};
+#[cfg(feature = "bytes")]
+pub use shared::{parse_request_bytes, parse_response_bytes, BytesRequest, BytesResponse};
+#[cfg(feature = "std")]
+pub use sink::HeaderSink;
+#[cfg(feature = "std")]
+pub use slices::{parse_request_slices, RequestSlices};
+#[cfg(feature = "std")]
+pub use spans::{parse_request_spans, RequestSpans};
+#[cfg(feature = "std")]
+pub use streaming::{ParseProgress, StreamingParser};
+#[cfg(feature = "std")]
+pub use url::RequestTarget;
+#[cfg(feature = "std")]
+pub use validate::ValidationError;
+#[cfg(feature = "std")]
+pub use version::HttpVersion;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// A byte that is not allowed at the current position was found.
+    /// `offset` is its index in the input.
+    UnexpectedByte { offset: usize, byte: u8 },
+    /// The input ended before the message was complete.
+    TruncatedInput,
+    /// The input is empty or holds only whitespace and line breaks.
+    Empty,
+    /// The status line of a response has fewer parts than expected.
+    MissingStatusLine,
+    /// The request line does not have a method, URL and version.
+    MalformedRequestLine,
+    /// The `Content-Length` header is not a valid number.
+    InvalidContentLength,
+    /// `Content-Length` is repeated with differing values.
+    DuplicateContentLength,
+    /// There are more headers than `ParserConfig::max_headers` or the
+    /// caller-provided buffer allows.
+    TooManyHeaders,
+    /// A header line is longer than `ParserConfig::max_header_bytes`.
+    HeaderTooLong,
+    /// The version is not of the form `HTTP/x.y`.
+    InvalidVersion,
+    /// The request line is longer than `ParserConfig::max_request_line`.
+    RequestLineTooLong,
+    /// The status code of a response is not exactly three digits.
+    InvalidStatusCode,
+    /// A header line has no colon.
+    MalformedHeader,
+    /// A header name is empty or contains a byte that is not allowed in a
+    /// token, such as a control character, space or separator.
+    InvalidHeaderName,
+    /// Fewer bytes follow the head than `Content-Length` declares.
+    IncompleteBody { expected: usize, got: usize },
+    /// A body is longer than `ParserConfig::max_body_bytes`.
+    BodyTooLong,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedByte { offset, byte } => {
+                write!(f, "unexpected byte {:#04x} at offset {}", byte, offset)
+            }
+            ParseError::TruncatedInput => write!(f, "truncated input"),
+            ParseError::Empty => write!(f, "empty input"),
+            ParseError::MissingStatusLine => write!(f, "missing status line"),
+            ParseError::MalformedRequestLine => write!(f, "malformed request line"),
+            ParseError::InvalidContentLength => write!(f, "invalid content length"),
+            ParseError::DuplicateContentLength => write!(f, "conflicting content lengths"),
+            ParseError::TooManyHeaders => write!(f, "too many headers"),
+            ParseError::HeaderTooLong => write!(f, "header line too long"),
+            ParseError::InvalidVersion => write!(f, "invalid http version"),
+            ParseError::RequestLineTooLong => write!(f, "request line too long"),
+            ParseError::InvalidStatusCode => write!(f, "invalid status code"),
+            ParseError::MalformedHeader => write!(f, "malformed header line"),
+            ParseError::InvalidHeaderName => write!(f, "invalid header name"),
+            ParseError::IncompleteBody { expected, got } => {
+                write!(
+                    f,
+                    "incomplete body: expected {} bytes, got {}",
+                    expected, got
+                )
+            }
+            ParseError::BodyTooLong => write!(f, "body too long"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Limits applied while parsing, to bound the work done on hostile input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// The maximum number of header lines.
+    pub max_headers: usize,
+    /// The maximum length of a single header line, excluding the line ending.
+    pub max_header_bytes: usize,
+    /// The maximum length of the request line, excluding the line ending.
+    pub max_request_line: usize,
+    /// Accept a tab wherever the request line expects a single space. RFC 7230
+    /// only allows a space, but some legacy clients send tabs.
+    pub allow_tab_separators: bool,
+    /// Skip header lines without a colon or with an invalid name instead of
+    /// failing with `ParseError::MalformedHeader` or
+    /// `ParseError::InvalidHeaderName`, and keep bare CRs in header lines
+    /// instead of failing with `ParseError::UnexpectedByte`.
+    pub tolerant: bool,
+    /// Store header names lowercased in `Headers`. A name containing an
+    /// uppercase letter is copied to do so, which allocates. The names as
+    /// sent are still available from `raw_headers`, and other
+    /// `HeaderSink`s keep them unchanged.
+    pub lowercase_headers: bool,
+    /// The maximum length of a body read by `parse_request_from_reader` or
+    /// buffered by `StreamingParser`, counting chunk framing. Parsing a
+    /// slice does not check it, since the body is already in memory.
+    pub max_body_bytes: usize,
+}
+
+impl Default for ParserConfig {
+    fn default() -> ParserConfig {
+        ParserConfig {
+            max_headers: 100,
+            max_header_bytes: 8 * 1024,
+            max_request_line: 8 * 1024,
+            allow_tab_separators: false,
+            tolerant: false,
+            lowercase_headers: false,
+            max_body_bytes: 8 * 1024 * 1024,
+        }
+    }
+}
+
+impl ParserConfig {
+    /// An upper bound on the size of a message head these limits allow,
+    /// counting a two-byte line ending per line and the blank line.
+    #[cfg(feature = "std")]
+    pub(crate) fn max_head(&self) -> usize {
+        let header_lines = self
+            .max_headers
+            .saturating_mul(self.max_header_bytes.saturating_add(2));
+        self.max_request_line
+            .saturating_add(4)
+            .saturating_add(header_lines)
+    }
+}
+
+// The bytes that delimit the parts of a message head. The parsers below
+// compare against these rather than literals.
+/// Separates the parts of the start line.
+const SP: u8 = b' ';
+/// Whitespace around header values, and a separator in tolerant request
+/// lines (`ParserConfig::allow_tab_separators`).
+const HTAB: u8 = b'\t';
+/// Ends a header name.
+const COLON: u8 = b':';
+/// Optionally precedes the `LF` that ends a line.
+const CR: u8 = b'\r';
+/// Ends a line.
+const LF: u8 = b'\n';
+
+/// A placeholder entry for the header buffer passed to the `*_raw` functions.
+pub const EMPTY_HEADER: (&[u8], &[u8]) = (b"", b"");
+
+type HeaderCallback<'a, 'c> = &'c mut dyn FnMut(&'a [u8], &'a [u8]) -> Result<(), ParseError>;
+
+/// Returns the inclusive `start..=end` range of `data`. A reversed or
+/// out-of-bounds range means a token was missing, which is reported as an
+/// error at `start`, where the token should have begun, rather than a panic.
+fn span(data: &[u8], start: usize, end: usize) -> Result<&[u8], ParseError> {
+    data.get(start..=end).ok_or_else(|| match data.get(start) {
+        Some(byte) => ParseError::UnexpectedByte {
+            offset: start,
+            byte: *byte,
+        },
+        None => ParseError::TruncatedInput,
+    })
+}
+
+/// Moves past the end of a token and its one-byte separator.
+fn offset(index: usize, by: usize) -> usize {
+    index.saturating_add(by)
+}
+
+/// Whether `name` is a non-empty RFC 7230 token, as header names must be.
+fn is_token(name: &[u8]) -> bool {
+    !name.is_empty()
+        && name
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(b))
+}
+
+/// Splits `HTTP/x.y` into its major and minor digits.
+fn parse_version(version: &[u8]) -> Option<(u8, u8)> {
+    match version {
+        [b'H', b'T', b'T', b'P', b'/', major, b'.', minor]
+            if major.is_ascii_digit() && minor.is_ascii_digit() =>
+        {
+            Some((major - b'0', minor - b'0'))
+        }
+        _ => None,
+    }
+}
+
+fn trim_whitespace(value: &[u8]) -> &[u8] {
+    let is_whitespace = |b: &u8| *b == SP || *b == HTAB;
+    let start = value.iter().position(|b| !is_whitespace(b));
+    let end = value.iter().rposition(|b| !is_whitespace(b));
+    match (start, end) {
+        (Some(start), Some(end)) => &value[start..=end],
+        _ => &value[..0],
+    }
+}
+
+/// Whether `data` has nothing but whitespace and line breaks, so no
+/// message can start in it.
+fn is_blank(data: &[u8]) -> bool {
+    data.iter().all(|b| matches!(*b, SP | HTAB | CR | LF))
+}
+
+/// Checks whether a comma-separated header value contains `token`,
+/// ignoring ASCII case. Line breaks count as whitespace, so a value still
+/// folded over several lines matches too.
+fn has_token(value: &[u8], token: &[u8]) -> bool {
+    let is_space = |b: &u8| matches!(*b, SP | HTAB | CR | LF);
+    value.split(|b| *b == b',').any(|item| {
+        let start = item.iter().position(|b| !is_space(b)).unwrap_or(item.len());
+        let end = item
+            .iter()
+            .rposition(|b| !is_space(b))
+            .map_or(start, |end| end + 1);
+        item[start..end].eq_ignore_ascii_case(token)
+    })
+}
+
+/// Applies the persistent-connection rules: `Connection: close` always
+/// closes, `keep-alive` keeps the connection open, and otherwise only
+/// HTTP/1.1 and later persist by default.
+#[cfg(feature = "std")]
+fn keep_alive(http_version: &[u8], connection: Option<&[u8]>) -> bool {
+    if let Some(connection) = connection {
+        if has_token(connection, b"close") {
+            return false;
+        }
+        if has_token(connection, b"keep-alive") {
+            return true;
+        }
+    }
+    parse_version(http_version).is_some_and(|version| version >= (1, 1))
+}
+
+#[cfg(feature = "std")]
+fn find_header<'k, 'v>(
+    fields: impl IntoIterator<Item = (&'k [u8], &'v [u8])>,
+    name: &[u8],
+) -> Option<&'v [u8]> {
+    fields
+        .into_iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+}
+
+#[cfg(feature = "std")]
+fn find_headers<'k, 'v>(
+    fields: impl IntoIterator<Item = (&'k [u8], &'v [u8])>,
+    name: &[u8],
+) -> Vec<&'v [u8]> {
+    fields
+        .into_iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+        .collect()
+}
+
+/// The framing headers of a message, gathered one header at a time so the
+/// body can be bounded without keeping the headers themselves.
+#[derive(Debug, Default)]
+struct BodyFraming {
+    /// Whether the first `Transfer-Encoding` names `chunked`, if one was sent.
+    chunked: Option<bool>,
+    /// The declared `Content-Length`, or the first error reading it.
+    content_length: Option<Result<usize, ParseError>>,
+}
+
+impl BodyFraming {
+    fn new<'k, 'v>(headers: impl IntoIterator<Item = (&'k [u8], &'v [u8])>) -> BodyFraming {
+        let mut framing = BodyFraming::default();
+        for (key, value) in headers {
+            framing.add(key, value);
+        }
+        framing
+    }
+
+    /// Records a header. Repeated `Content-Length` headers must all declare
+    /// the same length, since differing ones would let two parsers frame
+    /// the message differently (RFC 7230 §3.3.2).
+    fn add(&mut self, key: &[u8], value: &[u8]) {
+        if key.eq_ignore_ascii_case(b"transfer-encoding") {
+            self.chunked
+                .get_or_insert_with(|| has_token(value, b"chunked"));
+        } else if key.eq_ignore_ascii_case(b"content-length") {
+            self.content_length = match (self.content_length, parse_content_length(value)) {
+                (Some(Err(err)), _) | (_, Err(err)) => Some(Err(err)),
+                (Some(Ok(length)), Ok(value)) if length != value => {
+                    Some(Err(ParseError::DuplicateContentLength))
+                }
+                (_, Ok(value)) => Some(Ok(value)),
+            };
+        }
+    }
+
+    fn is_chunked(&self) -> bool {
+        self.chunked == Some(true)
+    }
+
+    fn content_length(&self) -> Result<Option<usize>, ParseError> {
+        self.content_length.transpose()
+    }
+
+    /// Whether either `Content-Length` or `Transfer-Encoding` was sent.
+    fn is_declared(&self) -> bool {
+        self.chunked.is_some() || self.content_length.is_some()
+    }
+}
+
+fn parse_content_length(value: &[u8]) -> Result<usize, ParseError> {
+    if value.is_empty() || !value.iter().all(u8::is_ascii_digit) {
+        return Err(ParseError::InvalidContentLength);
+    }
+    core::str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .ok_or(ParseError::InvalidContentLength)
+}
+
+/// Both framing headers at once is a classic request smuggling vector.
+#[cfg(feature = "std")]
+fn is_smuggling_suspect<'k, 'v>(
+    headers: impl IntoIterator<Item = (&'k [u8], &'v [u8])> + Clone,
+) -> bool {
+    find_header(headers.clone(), b"transfer-encoding").is_some()
+        && find_header(headers, b"content-length").is_some()
+}
+
+/// Bounds the body by its framing: a chunked body ends after the blank line
+/// closing its trailers, and any other body after `Content-Length` bytes.
+/// `Content-Length` is ignored for chunked messages as RFC 7230 §3.3.3
+/// requires. An unfinished chunked body is `ParseError::TruncatedInput`.
+fn body_slice<'a>(
+    data: &'a [u8],
+    start: usize,
+    framing: &BodyFraming,
+) -> Result<&'a [u8], ParseError> {
+    if framing.is_chunked() {
+        let len = chunked::body_len(&data[start..]).ok_or(ParseError::TruncatedInput)?;
+        return Ok(&data[start..start + len]);
+    }
+    let body = &data[start..];
+    match framing.content_length()? {
+        Some(len) if body.len() < len => Err(ParseError::IncompleteBody {
+            expected: len,
+            got: body.len(),
+        }),
+        Some(len) => Ok(&body[..len]),
+        None => Ok(body),
+    }
+}
+
+/// Bounds a request body. Unlike a response, a request without
+/// `Content-Length` or `Transfer-Encoding` has no body (RFC 7230 §3.3.3),
+/// so any bytes after its head belong to the next request.
+fn request_body_slice<'a>(
+    data: &'a [u8],
+    start: usize,
+    framing: &BodyFraming,
+) -> Result<&'a [u8], ParseError> {
+    if !framing.is_declared() {
+        return Ok(&data[start..start]);
+    }
+    body_slice(data, start, framing)
+}
+
+/// Inserts a parsed header into `headers`, lowercasing the name if
+/// `config` asks for it.
+#[cfg(feature = "std")]
+fn insert_header<'a>(
+    headers: &mut impl HeaderSink<'a>,
+    key: &'a [u8],
+    value: &'a [u8],
+    config: &ParserConfig,
+) {
+    if config.lowercase_headers {
+        headers.insert_lowercase(key, value);
+    } else {
+        headers.insert(key, value);
+    }
+}
+
+/// Runs `parse`, inserting each header into a new `H` and noting the
+/// headers that frame the body.
+#[cfg(feature = "std")]
+fn collect_headers<'a, H: HeaderSink<'a> + Default, T>(
+    config: &ParserConfig,
+    parse: impl FnOnce(HeaderCallback<'a, '_>) -> Result<T, ParseError>,
+) -> Result<(H, BodyFraming, T), ParseError> {
+    let mut headers = H::default();
+    let mut framing = BodyFraming::default();
+    let parsed = parse(&mut |key, value| {
+        framing.add(key, value);
+        insert_header(&mut headers, key, value, config);
+        Ok(())
+    })?;
+    Ok((headers, framing, parsed))
+}
+
+/// Parses a header block again into `Headers`, for messages whose sink
+/// does not keep the headers in order. The copy owns its bytes so that
+/// caching it leaves the message covariant over the input's lifetime. The
+/// block already parsed with `config`, so this cannot fail.
+#[cfg(feature = "std")]
+fn ordered_headers(header_block: &[u8], config: &ParserConfig) -> Headers<'static> {
+    let mut headers = Headers::default();
+    let _ = parse_headers(header_block, 0, config, true, &mut |key, value| {
+        insert_header(&mut headers, key, value, config);
+        Ok(())
+    });
+    headers.into_owned()
+}
+
+/// Copies headers into a fixed-size buffer, failing once it is full.
+/// Returns how many headers were stored along with the result of `parse`.
+fn fill_headers<'a, T>(
+    buf: &mut [(&'a [u8], &'a [u8])],
+    parse: impl FnOnce(HeaderCallback<'a, '_>) -> Result<T, ParseError>,
+) -> Result<(usize, T), ParseError> {
+    let mut count = 0;
+    let parsed = parse(&mut |key, value| {
+        let slot = buf.get_mut(count).ok_or(ParseError::TooManyHeaders)?;
+        *slot = (key, value);
+        count += 1;
+        Ok(())
+    })?;
+    Ok((count, parsed))
+}
+
+/// The start line of a message and where its header block and body begin.
+struct Head<'a> {
+    /// The three parts of the start line.
+    line: [&'a [u8]; 3],
+    /// The header lines, without the blank line that ends them.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    headers: &'a [u8],
+    /// The offset of the first body byte.
+    body: usize,
+}
+
+/// Scans the header block line by line, handing each header to `on_header`.
+/// Returns the offsets of the terminating blank line and of the first body
+/// byte. Each line, the blank one included, may end in CRLF or a bare LF
+/// regardless of how the others end.
+///
+/// A line starting with a space or tab continues the previous header
+/// (obs-fold); the value then spans the folded lines as they appear in the
+/// input, and `Headers` replaces each fold with a space when storing it.
+///
+/// With `at_eof`, `data` is known to hold the whole message, so input that
+/// ends right after a complete header line also ends the headers.
+fn parse_headers<'a>(
+    data: &'a [u8],
+    from: usize,
+    config: &ParserConfig,
+    at_eof: bool,
+    on_header: HeaderCallback<'a, '_>,
+) -> Result<(usize, usize), ParseError> {
+    let mut count = 0;
+    let mut emit = |(key, start, end): (&'a [u8], usize, usize)| {
+        count += 1;
+        if count > config.max_headers {
+            return Err(ParseError::TooManyHeaders);
+        }
+        on_header(key, trim_whitespace(span(data, start, end)?))
+    };
+    // The header whose value may still be continued on the next line.
+    let mut pending = None;
+    let mut is_end = false;
+    let mut blank = 0;
+    let mut header = 0;
+    let mut line_start = true;
+    let mut in_value = false;
+    let mut folded = false;
+    let mut start = 0;
+    let mut key = 0;
+    let mut bytes = data.iter().enumerate().skip(from);
+    #[cfg_attr(not(feature = "fast-scan"), allow(clippy::while_let_on_iterator))]
+    while let Some((i, current)) = bytes.next() {
+        if is_end {
+            if current == &LF {
+                if let Some(pending) = pending {
+                    emit(pending)?;
+                }
+                return Ok((blank, i + 1));
+            } else {
+                return Err(ParseError::UnexpectedByte {
+                    offset: i,
+                    byte: *current,
+                });
+            }
+        } else if current == &CR && data.get(i + 1).is_none_or(|next| *next == LF) {
+            // Part of a CRLF; the line itself ends on the LF.
+            if line_start {
+                is_end = true;
+                blank = i;
+            }
+        } else if current == &CR && !config.tolerant {
+            // A bare CR (RFC 7230 §3.5). Tolerant mode keeps it as a
+            // regular byte of the line.
+            return Err(ParseError::UnexpectedByte {
+                offset: i,
+                byte: CR,
+            });
+        } else if current == &LF {
+            if line_start {
+                // A bare LF blank line also ends the headers.
+                if let Some(pending) = pending {
+                    emit(pending)?;
+                }
+                return Ok((i, i + 1));
+            } else if folded {
+                if let Some((_, _, end)) = pending.as_mut() {
+                    *end = header;
+                }
+            } else if !in_value {
+                // A line without a colon is dropped in tolerant mode.
+                if !config.tolerant {
+                    return Err(ParseError::MalformedHeader);
+                }
+            } else if !is_token(&data[start..key]) {
+                // So is one whose name is not a token.
+                if !config.tolerant {
+                    return Err(ParseError::InvalidHeaderName);
+                }
+            } else {
+                if let Some(pending) = pending.take() {
+                    emit(pending)?;
+                }
+                pending = Some((&data[start..key], key + 1, header));
+            }
+            header = 0;
+            line_start = true;
+            in_value = false;
+            folded = false;
+        } else {
+            if line_start {
+                start = i;
+                line_start = false;
+                if current == &SP || current == &HTAB {
+                    if pending.is_none() {
+                        return Err(ParseError::UnexpectedByte {
+                            offset: i,
+                            byte: *current,
+                        });
+                    }
+                    folded = true;
+                }
+            } else if i - start >= config.max_header_bytes {
+                return Err(ParseError::HeaderTooLong);
+            }
+            if current == &COLON && !in_value && !folded {
+                key = i;
+                in_value = true;
+            }
+            // An empty value then spans from just past the colon to the colon.
+            header = i;
+            // The rest of a name or value only moves `header`, so jump to
+            // the next byte that does more.
+            #[cfg(feature = "fast-scan")]
+            {
+                let rest = &data[i + 1..];
+                let skip = if in_value || folded {
+                    memchr::memchr2(CR, LF, rest)
+                } else {
+                    memchr::memchr3(COLON, CR, LF, rest)
+                }
+                .unwrap_or(rest.len());
+                if skip > 0 {
+                    header = i + skip;
+                    if header - start >= config.max_header_bytes {
+                        return Err(ParseError::HeaderTooLong);
+                    }
+                    bytes.nth(skip - 1);
+                }
+            }
+        }
+    }
+    if at_eof && line_start && !is_end {
+        if let Some(pending) = pending {
+            emit(pending)?;
+        }
+        return Ok((data.len(), data.len()));
+    }
+    Err(ParseError::TruncatedInput)
 }
 
 enum ResponseParseState {
     HttpVersion,
     StatusCode,
     Status,
-    Headers { is_end: bool },
-    Body,
 }
 
-pub fn parse_response(data: &[u8]) -> Response {
+/// Parses the status line and headers of a response. The start line holds
+/// the version, status code and reason phrase. See `parse_headers` for
+/// `at_eof`.
+fn parse_response_head<'a>(
+    data: &'a [u8],
+    config: &ParserConfig,
+    at_eof: bool,
+    on_header: HeaderCallback<'a, '_>,
+) -> Result<Head<'a>, ParseError> {
+    if is_blank(data) {
+        return Err(ParseError::Empty);
+    }
     let mut state = ResponseParseState::HttpVersion;
     let mut http_version = 0;
     let mut status_code = 0;
     let mut status = 0;
-    let mut header = 0;
-    let mut headers_key: Vec<usize> = vec![];
-    let mut headers_value: Vec<usize> = vec![];
+    let mut headers = None;
     for (i, current) in data.iter().enumerate() {
         match state {
             ResponseParseState::HttpVersion => {
-                if current == &b' ' {
+                if current == &LF {
+                    return Err(ParseError::MissingStatusLine);
+                } else if current == &SP {
                     state = ResponseParseState::StatusCode;
                 } else {
                     http_version = i;
                 }
             }
             ResponseParseState::StatusCode => {
-                if current == &b' ' {
+                if current == &LF {
+                    // No reason phrase; leave the status slice empty.
+                    status = offset(status_code, 1);
+                    headers = Some(i + 1);
+                    break;
+                } else if current == &SP {
+                    status = i;
                     state = ResponseParseState::Status;
-                } else {
+                } else if current != &CR {
                     status_code = i;
                 }
             }
             ResponseParseState::Status => {
-                if current == &b'\n' {
-                    state = ResponseParseState::Headers { is_end: false };
-                } else {
+                if current == &LF {
+                    headers = Some(i + 1);
+                    break;
+                } else if current != &CR {
                     status = i;
                 }
             }
-            ResponseParseState::Headers { is_end } => {
-                if is_end {
-                    if current == &b'\n' {
-                        state = ResponseParseState::Body;
-                    } else {
-                        panic!("invalid state");
-                    }
-                } else if current == &b'\r' {
-                    state = ResponseParseState::Headers { is_end: true };
-                } else {
-                    if current == &b'\n' {
-                        headers_value.push(header);
-                        header = 0;
-                    } else if current == &b':' {
-                        headers_key.push(header);
-                        header = 0;
-                    } else {
-                        header = i;
-                    }
-                }
-            }
-            ResponseParseState::Body => {
-                break;
-            }
         }
     }
 
-    let http_version_slice = &data[..=http_version];
-    let status_code_slice = &data[http_version + 2..=status_code];
-    let status_slice = &data[status_code + 2..=status];
-
-    let mut headers = HashMap::new();
-    let mut last = status + 2;
-    for (key, value) in headers_key.iter().zip(headers_value) {
-        let key_slice = &data[last..=*key];
-        let value_slice = &data[key + 2..=value];
-        last = value + 2;
-        headers.insert(key_slice, value_slice);
-    }
-
-    let body_slice = &data[last + 2..];
+    let headers = headers.ok_or(ParseError::TruncatedInput)?;
+    let (blank, body) = parse_headers(data, headers, config, at_eof, on_header)?;
 
-    Response {
-        status: status_slice,
-        status_code: status_code_slice,
-        http_version: http_version_slice,
-        headers: headers,
-        body: body_slice,
+    let http_version_slice = span(data, 0, http_version)?;
+    let status_code_slice = span(data, offset(http_version, 2), status_code)?;
+    let status_slice = trim_whitespace(span(data, offset(status_code, 2), status)?);
+    parse_version(http_version_slice).ok_or(ParseError::InvalidVersion)?;
+    if status_code_slice.len() != 3 || !status_code_slice.iter().all(u8::is_ascii_digit) {
+        return Err(ParseError::InvalidStatusCode);
     }
-}
 
-pub struct Request<'a> {
-    pub method: &'a [u8],
-    pub url: &'a [u8],
-    pub http_version: &'a [u8],
-    pub headers: HashMap<&'a [u8], &'a [u8]>,
-    pub body: &'a [u8],
+    Ok(Head {
+        line: [http_version_slice, status_code_slice, status_slice],
+        headers: &data[headers..blank],
+        body,
+    })
 }
 
 enum RequestParseState {
     Method,
     Url,
     HttpVersion,
-    Headers { is_end: bool },
-    Body,
 }
 
-pub fn parse_request(data: &[u8]) -> Request {
+/// The tokens of a request line that were complete when parsing stopped.
+#[derive(Default)]
+struct LinePrefix<'a> {
+    method: Option<&'a [u8]>,
+    url: Option<&'a [u8]>,
+}
+
+/// Parses the request line and headers of a request. The start line holds
+/// the method, URL and version.
+fn parse_request_head<'a>(
+    data: &'a [u8],
+    config: &ParserConfig,
+    on_header: HeaderCallback<'a, '_>,
+) -> Result<Head<'a>, ParseError> {
+    parse_request_head_with_prefix(data, config, &mut LinePrefix::default(), on_header)
+}
+
+/// Like `parse_request_head`, but records the method and URL in `prefix`
+/// as soon as each is read, so they survive a later error.
+fn parse_request_head_with_prefix<'a>(
+    data: &'a [u8],
+    config: &ParserConfig,
+    prefix: &mut LinePrefix<'a>,
+    on_header: HeaderCallback<'a, '_>,
+) -> Result<Head<'a>, ParseError> {
+    if is_blank(data) {
+        return Err(ParseError::Empty);
+    }
     let mut state = RequestParseState::Method;
     let mut method = 0;
     let mut url = 0;
     let mut http_version = 0;
-    let mut header = 0;
-    let mut headers_key: Vec<usize> = vec![];
-    let mut headers_value: Vec<usize> = vec![];
-    for (i, current) in data.iter().enumerate() {
+    let mut token_len = 0;
+    let mut headers = None;
+    // Empty lines before the request line are ignored (RFC 7230 §3.5).
+    let mut line_start = 0;
+    while data[line_start..].starts_with(&[LF]) || data[line_start..].starts_with(&[CR, LF]) {
+        line_start += if data[line_start] == LF { 1 } else { 2 };
+    }
+    for (i, current) in data.iter().enumerate().skip(line_start) {
+        let is_space = current == &SP || (config.allow_tab_separators && current == &HTAB);
+        let is_separator = is_space || current == &LF;
+        if i - line_start >= config.max_request_line && current != &CR && current != &LF {
+            return Err(ParseError::RequestLineTooLong);
+        }
+        if is_separator && token_len == 0 {
+            return Err(ParseError::MalformedRequestLine);
+        }
         match state {
             RequestParseState::Method => {
-                if current == &b' ' {
+                if current == &LF {
+                    return Err(ParseError::MalformedRequestLine);
+                } else if is_space {
+                    prefix.method = Some(&data[line_start..i]);
                     state = RequestParseState::Url;
+                    token_len = 0;
                 } else {
                     method = i;
+                    token_len += 1;
                 }
             }
             RequestParseState::Url => {
-                if current == &b' ' {
+                if current == &LF {
+                    return Err(ParseError::MalformedRequestLine);
+                } else if is_space {
+                    prefix.url = Some(&data[offset(method, 2)..i]);
                     state = RequestParseState::HttpVersion;
+                    token_len = 0;
                 } else {
                     url = i;
+                    token_len += 1;
                 }
             }
             RequestParseState::HttpVersion => {
-                if current == &b'\n' {
-                    state = RequestParseState::Headers { is_end: false };
-                } else {
+                if current == &LF {
+                    headers = Some(i + 1);
+                    break;
+                } else if current != &CR {
                     http_version = i;
+                    token_len += 1;
                 }
             }
-            RequestParseState::Headers { is_end } => {
-                if is_end {
-                    if current == &b'\n' {
-                        state = RequestParseState::Body;
-                    } else {
-                        panic!("invalid state");
-                    }
-                } else if current == &b'\r' {
-                    state = RequestParseState::Headers { is_end: true };
-                } else {
-                    if current == &b'\n' {
-                        headers_value.push(header);
-                        header = 0;
-                    } else if current == &b':' {
-                        headers_key.push(header);
-                        header = 0;
-                    } else {
-                        header = i;
-                    }
-                }
-            }
-            RequestParseState::Body => {
-                break;
-            }
         }
     }
 
-    let method_slice = &data[..=method];
-    let url_slice = &data[method + 2..=url];
-    let http_version_slice = &data[url + 2..=http_version];
+    let headers = headers.ok_or(ParseError::TruncatedInput)?;
+    let (blank, body) = parse_headers(data, headers, config, false, on_header)?;
+
+    let method_slice = span(data, line_start, method)?;
+    let url_slice = span(data, offset(method, 2), url)?;
+    let http_version_slice = span(data, offset(url, 2), http_version)?;
+    parse_version(http_version_slice).ok_or(ParseError::InvalidVersion)?;
+
+    Ok(Head {
+        line: [method_slice, url_slice, http_version_slice],
+        headers: &data[headers..blank],
+        body,
+    })
+}
+
+/// A response parsed without allocating; `headers` borrows the buffer that
+/// was passed to `parse_response_raw`.
+#[derive(Debug)]
+pub struct RawResponse<'a, 'h> {
+    pub status: &'a [u8],
+    pub status_code: &'a [u8],
+    pub http_version: &'a [u8],
+    pub headers: &'h [(&'a [u8], &'a [u8])],
+    pub body: &'a [u8],
+}
+
+/// Parses a response, storing its headers in `headers` instead of
+/// allocating. Fails with `ParseError::TooManyHeaders` if the buffer is
+/// too small.
+pub fn parse_response_raw<'a, 'h>(
+    data: &'a [u8],
+    headers: &'h mut [(&'a [u8], &'a [u8])],
+) -> Result<RawResponse<'a, 'h>, ParseError> {
+    let (count, head) = fill_headers(headers, |on_header| {
+        parse_response_head(data, &ParserConfig::default(), true, on_header)
+    })?;
+    let headers = &headers[..count];
+    let [http_version, status_code, status] = head.line;
+    Ok(RawResponse {
+        status,
+        status_code,
+        http_version,
+        headers,
+        body: body_slice(data, head.body, &BodyFraming::new(headers.iter().copied()))?,
+    })
+}
+
+/// A request parsed without allocating; `headers` borrows the buffer that
+/// was passed to `parse_request_raw` or `parse_request_into`.
+#[derive(Debug)]
+pub struct RawRequest<'a, 'h> {
+    pub method: &'a [u8],
+    pub url: &'a [u8],
+    pub http_version: &'a [u8],
+    pub headers: &'h [(&'a [u8], &'a [u8])],
+    pub body: &'a [u8],
+}
+
+/// Parses a request, storing its headers in `headers` instead of
+/// allocating. Fails with `ParseError::TooManyHeaders` if the buffer is
+/// too small.
+pub fn parse_request_raw<'a, 'h>(
+    data: &'a [u8],
+    headers: &'h mut [(&'a [u8], &'a [u8])],
+) -> Result<RawRequest<'a, 'h>, ParseError> {
+    let (count, head) = fill_headers(headers, |on_header| {
+        parse_request_head(data, &ParserConfig::default(), on_header)
+    })?;
+    let headers = &headers[..count];
+    let [method, url, http_version] = head.line;
+    Ok(RawRequest {
+        method,
+        url,
+        http_version,
+        headers,
+        body: request_body_slice(data, head.body, &BodyFraming::new(headers.iter().copied()))?,
+    })
+}
+
+/// Parses a request, storing its headers in `headers`, which is cleared
+/// first. Reusing one vector across calls avoids allocating for the
+/// headers of every request once it has grown large enough.
+#[cfg(feature = "std")]
+pub fn parse_request_into<'a, 'h>(
+    data: &'a [u8],
+    headers: &'h mut Vec<(&'a [u8], &'a [u8])>,
+) -> Result<RawRequest<'a, 'h>, ParseError> {
+    headers.clear();
+    let head = parse_request_head(data, &ParserConfig::default(), &mut |key, value| {
+        headers.push((key, value));
+        Ok(())
+    })?;
+    let headers: &'h Vec<_> = headers;
+    let [method, url, http_version] = head.line;
+    Ok(RawRequest {
+        method,
+        url,
+        http_version,
+        headers,
+        body: request_body_slice(data, head.body, &BodyFraming::new(headers.iter().copied()))?,
+    })
+}
+
+/// A parsed response. `headers` is a `Headers` unless another `HeaderSink`
+/// is chosen with `parse_response_with_headers`.
+///
+/// Header lookups borrow from the response rather than the input, since a
+/// value folded over several lines is stored unfolded.
+#[cfg(feature = "std")]
+pub struct Response<'a, H = Headers<'a>> {
+    pub status: &'a [u8],
+    pub status_code: &'a [u8],
+    pub http_version: &'a [u8],
+    pub headers: H,
+    pub body: &'a [u8],
+    /// The headers in order, parsed again from `header_block` on first use
+    /// when `headers` cannot serve lookups.
+    fields: OnceLock<Headers<'static>>,
+    config: ParserConfig,
+    header_block: &'a [u8],
+    headers_end: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// The ordered headers every lookup goes through: `headers` itself when
+    /// it is a `Headers`, or a copy built for another sink when first needed.
+    fn fields(&self) -> &Headers<'a> {
+        match self.headers.ordered() {
+            Some(headers) => headers,
+            None => self
+                .fields
+                .get_or_init(|| ordered_headers(self.header_block, &self.config)),
+        }
+    }
+
+    /// Looks up a header value, ignoring ASCII case in the name.
+    pub fn header(&self, name: &str) -> Option<&[u8]> {
+        self.fields().get(name)
+    }
+
+    /// Returns every value of a repeated header, in the order they appeared.
+    pub fn header_all(&self, name: &str) -> Vec<&[u8]> {
+        self.fields().get_all(name)
+    }
 
-    let mut headers = HashMap::new();
-    let mut last = http_version + 2;
-    for (key, value) in headers_key.iter().zip(headers_value) {
-        let key_slice = &data[last..=*key];
-        let value_slice = &data[key + 2..=value];
-        last = value + 2;
-        headers.insert(key_slice, value_slice);
+    /// Iterates over the headers in the order they appeared in the input.
+    pub fn headers_ordered(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.fields().iter()
     }
 
-    let body_slice = &data[last + 2..];
+    /// Returns the header lines exactly as they appeared in the input, up to
+    /// but not including the blank line that ends them.
+    pub fn raw_headers(&self) -> &'a [u8] {
+        self.header_block
+    }
 
-    Request {
-        method: method_slice,
-        url: url_slice,
-        http_version: http_version_slice,
-        headers: headers,
-        body: body_slice,
+    /// Returns the offset just past the blank line that ends the headers,
+    /// which is where the body starts in the parsed buffer.
+    pub fn headers_end(&self) -> usize {
+        self.headers_end
+    }
+
+    /// Returns how many bytes the whole message occupied in the parsed
+    /// buffer: the start line, headers, blank line and body.
+    pub fn wire_len(&self) -> usize {
+        self.headers_end + self.body.len()
+    }
+
+    /// Returns the major and minor version numbers.
+    pub fn version(&self) -> Option<(u8, u8)> {
+        parse_version(self.http_version)
+    }
+
+    /// Whether the connection stays open after this message.
+    pub fn keep_alive(&self) -> bool {
+        keep_alive(self.http_version, self.header("connection"))
+    }
+
+    /// Whether both `Transfer-Encoding` and `Content-Length` are present.
+    /// The body is framed by the transfer-coding, but a proxy should treat
+    /// such a message as a possible smuggling attempt.
+    pub fn is_smuggling_suspect(&self) -> bool {
+        is_smuggling_suspect(self.fields().iter())
+    }
+
+    /// Parses the status code, which must be exactly three ASCII digits.
+    /// Parsing already checks this, so `None` only comes from a
+    /// `status_code` set by hand afterwards.
+    pub fn status_code_u16(&self) -> Option<u16> {
+        if self.status_code.len() != 3 || !self.status_code.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        Some(
+            self.status_code
+                .iter()
+                .fold(0, |acc, b| acc * 10 + u16::from(b - b'0')),
+        )
+    }
+
+    /// Returns the body with any chunked transfer-coding removed.
+    pub fn decoded_body(&self) -> Vec<u8> {
+        if BodyFraming::new(self.fields().iter()).is_chunked() {
+            chunked::decode(self.body)
+        } else {
+            self.body.to_vec()
+        }
+    }
+
+    /// Returns the trailer fields sent after the last chunk of a chunked
+    /// body. Other messages have none.
+    pub fn trailers(&self) -> Headers<'a> {
+        if BodyFraming::new(self.fields().iter()).is_chunked() {
+            chunked::trailers(self.body)
+        } else {
+            Headers::default()
+        }
+    }
+
+    /// Returns the raw body as a string if it is valid UTF-8.
+    pub fn body_str(&self) -> Result<&'a str, Utf8Error> {
+        core::str::from_utf8(self.body)
+    }
+
+    /// Returns the raw body as a string, replacing invalid UTF-8.
+    pub fn body_str_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.body)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parses a complete response. `data` is taken to end at the end of the
+/// message, so a response whose input stops right after its last header
+/// line parses with an empty body. Like a request, the response borrows
+/// from `data` alone.
+#[cfg(feature = "std")]
+pub fn parse_response(data: &[u8]) -> Result<Response<'_>, ParseError> {
+    Response::try_from(data)
+}
 
-    #[test]
-    fn test_parse_request() {
-        let input = b"GET /index HTTP/1.1\nhost:test.com\nContent-Type:text/html\n\r\nabc";
-        let result = parse_request(input);
-        assert_eq!(result.method, b"GET");
-        assert_eq!(result.url, b"/index");
-        assert_eq!(result.http_version, b"HTTP/1.1");
-        assert_eq!(result.headers.len(), 2);
-        assert_eq!(result.headers.get(&b"host"[..]).unwrap(), &&b"test.com"[..]);
-        assert_eq!(
-            result.headers.get(&b"Content-Type"[..]).unwrap(),
-            &&b"text/html"[..]
-        );
-        assert_eq!(result.body, b"abc");
+/// The same as `parse_response`.
+#[cfg(feature = "std")]
+impl<'a> TryFrom<&'a [u8]> for Response<'a> {
+    type Error = ParseError;
+
+    fn try_from(data: &'a [u8]) -> Result<Response<'a>, ParseError> {
+        parse_response_with_config(data, &ParserConfig::default())
     }
+}
 
-    #[test]
-    fn test_parse_response() {
-        let input = b"HTTP/1.1 200 OK\nContent-Length:88\nContent-Type:text/html\n\r\nbody123";
-        let result = parse_response(input);
-        assert_eq!(result.status_code, b"200");
-        assert_eq!(result.status, b"OK");
-        assert_eq!(result.http_version, b"HTTP/1.1");
-        assert_eq!(result.headers.len(), 2);
-        assert_eq!(
-            result.headers.get(&b"Content-Length"[..]).unwrap(),
-            &&b"88"[..]
-        );
-        assert_eq!(
-            result.headers.get(&b"Content-Type"[..]).unwrap(),
-            &&b"text/html"[..]
-        );
-        assert_eq!(result.body, b"body123");
+/// Like `parse_response`, but with custom limits.
+#[cfg(feature = "std")]
+pub fn parse_response_with_config<'a>(
+    data: &'a [u8],
+    config: &ParserConfig,
+) -> Result<Response<'a>, ParseError> {
+    parse_response_inner(data, config, true, None).map(|(response, _)| response)
+}
+
+/// Parses a response and also returns the number of bytes it occupied, which is
+/// the offset at which a following pipelined message starts.
+#[cfg(feature = "std")]
+pub fn parse_response_with_len(data: &[u8]) -> Result<(Response<'_>, usize), ParseError> {
+    parse_response_inner(data, &ParserConfig::default(), true, None)
+}
+
+/// Like `parse_response`, but collects the headers into `H` instead of
+/// `Headers`, e.g. a `BTreeMap` for sorted keys.
+#[cfg(feature = "std")]
+pub fn parse_response_with_headers<'a, H: HeaderSink<'a> + Default>(
+    data: &'a [u8],
+) -> Result<Response<'a, H>, ParseError> {
+    parse_response_inner(data, &ParserConfig::default(), true, None).map(|(response, _)| response)
+}
+
+/// Parses a response to a request made with `method`. Responses to `HEAD`,
+/// successful `CONNECT` responses and 1xx, 204 and 304 responses never have
+/// a body (RFC 7230 §3.3.3), so their body is empty whatever the framing
+/// headers say and any following bytes are left for the next message.
+#[cfg(feature = "std")]
+pub fn parse_response_for_method(data: &[u8], method: Method) -> Result<Response<'_>, ParseError> {
+    parse_response_inner(data, &ParserConfig::default(), true, Some(&method))
+        .map(|(response, _)| response)
+}
+
+/// An interim 1xx response, such as `100 Continue`, sent before the final
+/// response. It never has a body.
+#[cfg(feature = "std")]
+pub type InterimResponse<'a> = Response<'a>;
+
+/// Parses any interim 1xx responses at the start of `data` and then the
+/// final response, as a client that sent `Expect: 100-continue` receives
+/// them. `101 Switching Protocols` is final, since the connection no longer
+/// speaks HTTP/1.x after it. Input that ends before the final response is
+/// `ParseError::TruncatedInput`.
+#[cfg(feature = "std")]
+pub fn parse_response_skipping_informational(
+    mut data: &[u8],
+) -> Result<(Vec<InterimResponse<'_>>, Response<'_>), ParseError> {
+    let mut interim = vec![];
+    loop {
+        let mut response = match parse_response(data) {
+            // The final response has yet to arrive.
+            Err(ParseError::Empty) if !interim.is_empty() => {
+                return Err(ParseError::TruncatedInput)
+            }
+            result => result?,
+        };
+        if !response.status_code.starts_with(b"1") || response.status_code == b"101" {
+            return Ok((interim, response));
+        }
+        let len = response.headers_end();
+        response.body = &data[len..len];
+        interim.push(response);
+        data = &data[len..];
+    }
+}
+
+/// Whether a response with `status_code` to a `method` request is bodyless.
+#[cfg(feature = "std")]
+fn is_bodyless(method: &Method, status_code: &[u8]) -> bool {
+    match status_code {
+        [b'1', ..] | b"204" | b"304" => true,
+        [b'2', ..] => *method == Method::Head || *method == Method::Connect,
+        _ => *method == Method::Head,
+    }
+}
+
+#[cfg(feature = "std")]
+fn parse_response_inner<'a, H: HeaderSink<'a> + Default>(
+    data: &'a [u8],
+    config: &ParserConfig,
+    at_eof: bool,
+    method: Option<&Method>,
+) -> Result<(Response<'a, H>, usize), ParseError> {
+    let (headers, framing, head): (H, _, _) = collect_headers(config, |on_header| {
+        parse_response_head(data, config, at_eof, on_header)
+    })?;
+    let [http_version, status_code, status] = head.line;
+
+    let body_slice = match method {
+        Some(method) if is_bodyless(method, status_code) => &data[head.body..head.body],
+        _ => body_slice(data, head.body, &framing)?,
+    };
+
+    let response = Response {
+        status,
+        status_code,
+        http_version,
+        headers,
+        body: body_slice,
+        fields: OnceLock::new(),
+        config: *config,
+        header_block: head.headers,
+        headers_end: head.body,
+    };
+
+    Ok((response, head.body + body_slice.len()))
+}
+
+/// Like `parse_response`, but returns `Ok(None)` when `data` does not yet
+/// hold a complete message, so the caller can read more and try again.
+#[cfg(feature = "std")]
+pub fn parse_response_partial(data: &[u8]) -> Result<Option<Response<'_>>, ParseError> {
+    match parse_response_inner(data, &ParserConfig::default(), false, None) {
+        Ok((response, _)) => Ok(Some(response)),
+        Err(ParseError::TruncatedInput | ParseError::Empty | ParseError::IncompleteBody { .. }) => {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// A parsed request. `headers` is a `Headers` unless another `HeaderSink`
+/// is chosen with `parse_request_with_headers`.
+///
+/// Header lookups borrow from the request rather than the input, since a
+/// value folded over several lines is stored unfolded.
+#[cfg(feature = "std")]
+pub struct Request<'a, H = Headers<'a>> {
+    pub method: &'a [u8],
+    pub url: &'a [u8],
+    pub http_version: &'a [u8],
+    pub headers: H,
+    pub body: &'a [u8],
+    /// The headers in order, parsed again from `header_block` on first use
+    /// when `headers` cannot serve lookups.
+    fields: OnceLock<Headers<'static>>,
+    config: ParserConfig,
+    header_block: &'a [u8],
+    headers_end: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// The ordered headers every lookup goes through: `headers` itself when
+    /// it is a `Headers`, or a copy built for another sink when first needed.
+    fn fields(&self) -> &Headers<'a> {
+        match self.headers.ordered() {
+            Some(headers) => headers,
+            None => self
+                .fields
+                .get_or_init(|| ordered_headers(self.header_block, &self.config)),
+        }
+    }
+
+    /// Returns the request method as a `Method`.
+    pub fn method_enum(&self) -> Method {
+        Method::from(self.method)
+    }
+
+    /// Returns the major and minor version numbers.
+    pub fn version(&self) -> Option<(u8, u8)> {
+        parse_version(self.http_version)
+    }
+
+    /// Whether the connection stays open after this message.
+    pub fn keep_alive(&self) -> bool {
+        keep_alive(self.http_version, self.header("connection"))
+    }
+
+    /// Whether the client waits for a `100 Continue` response before
+    /// sending the body.
+    pub fn expects_continue(&self) -> bool {
+        self.header_all("expect")
+            .into_iter()
+            .any(|value| has_token(value, b"100-continue"))
+    }
+
+    /// Whether the request declares a body with `Content-Length` or
+    /// `Transfer-Encoding` (RFC 7230 §3.3). Without either, any bytes after
+    /// the head belong to the next message rather than to this request.
+    pub fn has_body(&self) -> bool {
+        self.fields().contains("content-length") || self.fields().contains("transfer-encoding")
+    }
+
+    /// Whether both `Transfer-Encoding` and `Content-Length` are present.
+    /// The body is framed by the transfer-coding, but a proxy should treat
+    /// such a message as a possible smuggling attempt.
+    pub fn is_smuggling_suspect(&self) -> bool {
+        is_smuggling_suspect(self.fields().iter())
+    }
+
+    /// Looks up a header value, ignoring ASCII case in the name.
+    pub fn header(&self, name: &str) -> Option<&[u8]> {
+        self.fields().get(name)
+    }
+
+    /// Returns every value of a repeated header, in the order they appeared.
+    pub fn header_all(&self, name: &str) -> Vec<&[u8]> {
+        self.fields().get_all(name)
+    }
+
+    /// Iterates over the headers in the order they appeared in the input.
+    pub fn headers_ordered(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.fields().iter()
+    }
+
+    /// Returns the header lines exactly as they appeared in the input, up to
+    /// but not including the blank line that ends them.
+    pub fn raw_headers(&self) -> &'a [u8] {
+        self.header_block
+    }
+
+    /// Returns the offset just past the blank line that ends the headers,
+    /// which is where the body starts in the parsed buffer.
+    pub fn headers_end(&self) -> usize {
+        self.headers_end
+    }
+
+    /// Returns how many bytes the whole message occupied in the parsed
+    /// buffer: the start line, headers, blank line and body.
+    pub fn wire_len(&self) -> usize {
+        self.headers_end + self.body.len()
+    }
+
+    /// Returns the body as a string if it is valid UTF-8.
+    pub fn body_str(&self) -> Result<&'a str, Utf8Error> {
+        core::str::from_utf8(self.body)
+    }
+
+    /// Returns the body as a string, replacing invalid UTF-8.
+    pub fn body_str_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.body)
+    }
+}
+
+/// Parses a request.
+///
+/// The request borrows from `data` and from nothing else, so it can be
+/// kept for as long as `data` is, e.g. while a lock guard over the buffer
+/// is held, but not past it.
+#[cfg(feature = "std")]
+pub fn parse_request(data: &[u8]) -> Result<Request<'_>, ParseError> {
+    Request::try_from(data)
+}
+
+/// The same as `parse_request`.
+#[cfg(feature = "std")]
+impl<'a> TryFrom<&'a [u8]> for Request<'a> {
+    type Error = ParseError;
+
+    fn try_from(data: &'a [u8]) -> Result<Request<'a>, ParseError> {
+        parse_request_with_config(data, &ParserConfig::default())
+    }
+}
+
+/// Like `parse_request`, but with custom limits.
+#[cfg(feature = "std")]
+pub fn parse_request_with_config<'a>(
+    data: &'a [u8],
+    config: &ParserConfig,
+) -> Result<Request<'a>, ParseError> {
+    parse_request_inner(data, config).map(|(request, _)| request)
+}
+
+/// The error from `parse_request_with_partial`, along with the parts of the
+/// request line that were read before parsing failed.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialRequest<'a> {
+    pub error: ParseError,
+    pub method: Option<&'a [u8]>,
+    pub url: Option<&'a [u8]>,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for PartialRequest<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PartialRequest<'_> {}
+
+#[cfg(feature = "std")]
+impl From<PartialRequest<'_>> for ParseError {
+    fn from(partial: PartialRequest<'_>) -> ParseError {
+        partial.error
+    }
+}
+
+/// Like `parse_request_with_config`, but on failure still returns the
+/// method and URL if they were read, e.g. to log a request whose version
+/// is garbage.
+#[cfg(feature = "std")]
+pub fn parse_request_with_partial<'a>(
+    data: &'a [u8],
+    config: &ParserConfig,
+) -> Result<Request<'a>, PartialRequest<'a>> {
+    let mut prefix = LinePrefix::default();
+    parse_request_inner_with_prefix(data, config, &mut prefix)
+        .map(|(request, _)| request)
+        .map_err(|error| PartialRequest {
+            error,
+            method: prefix.method,
+            url: prefix.url,
+        })
+}
+
+/// Parses a request and also returns the number of bytes it occupied, which is
+/// the offset at which a following pipelined message starts.
+#[cfg(feature = "std")]
+pub fn parse_request_with_len(data: &[u8]) -> Result<(Request<'_>, usize), ParseError> {
+    parse_request_inner(data, &ParserConfig::default())
+}
+
+/// Like `parse_request`, but collects the headers into `H` instead of
+/// `Headers`, e.g. a `BTreeMap` for sorted keys.
+#[cfg(feature = "std")]
+pub fn parse_request_with_headers<'a, H: HeaderSink<'a> + Default>(
+    data: &'a [u8],
+) -> Result<Request<'a, H>, ParseError> {
+    parse_request_inner(data, &ParserConfig::default()).map(|(request, _)| request)
+}
+
+#[cfg(feature = "std")]
+fn parse_request_inner<'a, H: HeaderSink<'a> + Default>(
+    data: &'a [u8],
+    config: &ParserConfig,
+) -> Result<(Request<'a, H>, usize), ParseError> {
+    parse_request_inner_with_prefix(data, config, &mut LinePrefix::default())
+}
+
+#[cfg(feature = "std")]
+fn parse_request_inner_with_prefix<'a, H: HeaderSink<'a> + Default>(
+    data: &'a [u8],
+    config: &ParserConfig,
+    prefix: &mut LinePrefix<'a>,
+) -> Result<(Request<'a, H>, usize), ParseError> {
+    let (headers, framing, head): (H, _, _) = collect_headers(config, |on_header| {
+        parse_request_head_with_prefix(data, config, prefix, on_header)
+    })?;
+    let [method, url, http_version] = head.line;
+
+    let body_slice = request_body_slice(data, head.body, &framing)?;
+
+    let request = Request {
+        method,
+        url,
+        http_version,
+        headers,
+        body: body_slice,
+        fields: OnceLock::new(),
+        config: *config,
+        header_block: head.headers,
+        headers_end: head.body,
+    };
+
+    Ok((request, head.body + body_slice.len()))
+}
+
+/// Like `parse_request`, but returns `Ok(None)` when `data` does not yet
+/// hold a complete message, so the caller can read more and try again.
+#[cfg(feature = "std")]
+pub fn parse_request_partial(data: &[u8]) -> Result<Option<Request<'_>>, ParseError> {
+    match parse_request(data) {
+        Ok(request) => Ok(Some(request)),
+        Err(ParseError::TruncatedInput | ParseError::Empty | ParseError::IncompleteBody { .. }) => {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Parses every pipelined request in `data`.
+#[cfg(feature = "std")]
+pub fn parse_requests(data: &[u8]) -> Result<Vec<Request<'_>>, ParseError> {
+    RequestIter::new(data).collect()
+}
+
+/// Parses the pipelined requests in a buffer one at a time, as
+/// `parse_requests` does. It ends when the buffer is used up, or after
+/// yielding the error for a message that does not parse, such as
+/// `ParseError::TruncatedInput` for one cut off at the end of the buffer.
+#[cfg(feature = "std")]
+pub struct RequestIter<'a> {
+    data: &'a [u8],
+    failed: bool,
+}
+
+#[cfg(feature = "std")]
+impl<'a> RequestIter<'a> {
+    pub fn new(data: &'a [u8]) -> RequestIter<'a> {
+        RequestIter {
+            data,
+            failed: false,
+        }
+    }
+
+    /// Returns the bytes not yet parsed.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Iterator for RequestIter<'a> {
+    type Item = Result<Request<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() || self.failed {
+            return None;
+        }
+        let (request, len) = match parse_request_with_len(self.data) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                self.failed = true;
+                return Some(Err(err));
+            }
+        };
+        self.data = &self.data[len..];
+        Some(Ok(request))
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::iter::FusedIterator for RequestIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_request() {
+        let input =
+            b"GET /index HTTP/1.1\nhost:test.com\nContent-Type:text/html\nContent-Length:3\n\r\nabc";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.method, b"GET");
+        assert_eq!(result.url, b"/index");
+        assert_eq!(result.http_version, b"HTTP/1.1");
+        assert_eq!(result.headers.len(), 3);
+        assert_eq!(result.headers.get("host").unwrap(), b"test.com");
+        assert_eq!(result.headers.get("Content-Type").unwrap(), b"text/html");
+        assert_eq!(result.body, b"abc");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_response() {
+        let input = b"HTTP/1.1 200 OK\nContent-Length:7\nContent-Type:text/html\n\r\nbody123";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.status_code, b"200");
+        assert_eq!(result.status, b"OK");
+        assert_eq!(result.http_version, b"HTTP/1.1");
+        assert_eq!(result.headers.len(), 2);
+        assert_eq!(result.headers.get("Content-Length").unwrap(), b"7");
+        assert_eq!(result.headers.get("Content-Type").unwrap(), b"text/html");
+        assert_eq!(result.body, b"body123");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_malformed_input() {
+        assert_eq!(
+            parse_request(b"GET /index HTTP/1.1\nhost:test.com\n\rx").err(),
+            Some(ParseError::UnexpectedByte {
+                offset: 34,
+                byte: b'\r'
+            })
+        );
+        assert_eq!(
+            parse_request(b"GET /index HTTP/1.1\nhost:test.com\n").err(),
+            Some(ParseError::TruncatedInput)
+        );
+        assert_eq!(
+            parse_response(b"HTTP/1.1\n\r\n").err(),
+            Some(ParseError::MissingStatusLine)
+        );
+
+        let input = b"HTTP/1.1 200 OK\nContent-Length:88\n\r\nbody123";
+        for end in 0..input.len() {
+            let _ = parse_response(&input[..end]);
+            let _ = parse_request(&input[end..]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_delimiters_inside_tokens() {
+        let input = b"GET http://x:80/a:b HTTP/1.1\r\nHost: x:80\r\nX-A: b c\td\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.url, b"http://x:80/a:b");
+        assert_eq!(result.header("host"), Some(&b"x:80"[..]));
+        assert_eq!(result.header("x-a"), Some(&b"b c\td"[..]));
+
+        let input = b"HTTP/1.1 404 Not  Found: x\nA:\t:b: \n\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.status, b"Not  Found: x");
+        assert_eq!(result.header("a"), Some(&b":b:"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_empty_input() {
+        for input in [&b""[..], b" ", b"\n", b" \r\n\t\r\n"] {
+            assert_eq!(parse_request(input).err(), Some(ParseError::Empty));
+            assert_eq!(parse_response(input).err(), Some(ParseError::Empty));
+            let mut headers = [EMPTY_HEADER; 1];
+            assert_eq!(
+                parse_request_raw(input, &mut headers).err(),
+                Some(ParseError::Empty)
+            );
+            assert!(parse_request_partial(input).unwrap().is_none());
+            assert!(parse_response_partial(input).unwrap().is_none());
+        }
+        assert_eq!(ParseError::Empty.to_string(), "empty input");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_mixed_line_endings() {
+        let input = b"GET / HTTP/1.1\nHost:x\r\nAccept:y\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.header("host"), Some(&b"x"[..]));
+        assert_eq!(result.header("accept"), Some(&b"y"[..]));
+        assert_eq!(result.headers_end(), input.len());
+
+        let input = b"HTTP/1.1 200 OK\r\nA:1\nB:2\r\nC:3\n\nbody";
+        let result = parse_response(input).unwrap();
+        let values: Vec<_> = result.headers_ordered().map(|(_, value)| value).collect();
+        assert_eq!(values, [&b"1"[..], b"2", b"3"]);
+        assert_eq!(result.body, b"body");
+
+        let input = b"GET / HTTP/1.1\r\nA:1\nB:2\r\n\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.headers.len(), 2);
+        assert_eq!(result.raw_headers(), b"A:1\nB:2\r\n");
+
+        let input = b"GET / HTTP/1.1\r\nA: 1\n 2\r\n\t3\nB:4\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.header("a"), Some(&b"1 2 3"[..]));
+        assert_eq!(result.header("b"), Some(&b"4"[..]));
+
+        let mut parser = StreamingParser::new();
+        parser.push(b"POST / HTTP/1.1\nContent-Length:2\r\nA:1\n\r\nhi");
+        match parser.poll() {
+            ParseProgress::Complete(request) => assert_eq!(request.body, b"hi"),
+            progress => panic!("unexpected {:?}", progress),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_crlf() {
+        let lf = parse_request(b"GET / HTTP/1.1\nhost:x\nContent-Length:4\n\r\nbody").unwrap();
+        let crlf =
+            parse_request(b"GET / HTTP/1.1\r\nhost:x\r\nContent-Length:4\r\n\r\nbody").unwrap();
+        assert_eq!(crlf.http_version, b"HTTP/1.1");
+        assert_eq!(crlf.http_version, lf.http_version);
+        assert_eq!(crlf.headers, lf.headers);
+        assert_eq!(crlf.body, b"body");
+
+        let result = parse_response(b"HTTP/1.1 200 OK\r\nhost:x\r\n\r\nbody").unwrap();
+        assert_eq!(result.status, b"OK");
+        assert_eq!(result.headers.get("host").unwrap(), b"x");
+        assert_eq!(result.body, b"body");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_content_length() {
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length:4\r\n\r\nbodyHTTP/1.1 200 OK";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.body, b"body");
+
+        let input = b"POST / HTTP/1.1\r\ncontent-length:2\r\n\r\nabc";
+        assert_eq!(parse_request(input).unwrap().body, b"ab");
+
+        let input = b"POST / HTTP/1.1\r\nContent-Length:3\r\n\r\nabc";
+        assert_eq!(parse_request(input).unwrap().body, b"abc");
+
+        let input = b"POST / HTTP/1.1\r\nContent-Length:10\r\n\r\nabc";
+        let err = parse_request(input).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::IncompleteBody {
+                expected: 10,
+                got: 3
+            }
+        );
+        assert_eq!(err.to_string(), "incomplete body: expected 10 bytes, got 3");
+
+        let input = b"POST / HTTP/1.1\r\nContent-Length:ten\r\n\r\nabc";
+        assert_eq!(
+            parse_request(input).err(),
+            Some(ParseError::InvalidContentLength)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_duplicate_content_length() {
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 3\r\nContent-Length: 5\r\n\r\nabcde";
+        assert_eq!(
+            parse_request(input).err(),
+            Some(ParseError::DuplicateContentLength)
+        );
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\ncontent-length: 4\r\n\r\nabcd";
+        assert_eq!(
+            parse_response(input).err(),
+            Some(ParseError::DuplicateContentLength)
+        );
+
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 3\r\nContent-Length: 3\r\n\r\nabcde";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.body, b"abc");
+        assert_eq!(result.header_all("content-length").len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_body_with_blank_lines() {
+        let input =
+            b"POST / HTTP/1.1\r\nContent-Length: 20\r\n\r\na\r\n\r\nb\n\nGET / HTTP/1.1\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.body, b"a\r\n\r\nb\n\nGET / HTTP/1");
+        assert_eq!(result.headers.len(), 1);
+
+        let input = b"HTTP/1.1 200 OK\r\n\r\nHost: x\r\n\r\nmore";
+        let result = parse_response(input).unwrap();
+        assert!(result.headers.is_empty());
+        assert_eq!(result.body, b"Host: x\r\n\r\nmore");
+
+        let input = b"POST /a HTTP/1.1\r\nContent-Length: 19\r\n\r\nGET /b HTTP/1.1\r\n\r\nGET /c HTTP/1.1\r\n\r\n";
+        let requests = parse_requests(input).unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].body, b"GET /b HTTP/1.1\r\n\r\n");
+        assert_eq!(requests[1].url, b"/c");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_decoded_body() {
+        let input =
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding:chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.decoded_body(), b"abcde");
+
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length:3\r\n\r\nabc";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.decoded_body(), b"abc");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_header_case_insensitive() {
+        let input = b"GET / HTTP/1.1\r\nHost:test.com\r\nContent-Type:text/html\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.header("host"), Some(&b"test.com"[..]));
+        assert_eq!(result.header("CONTENT-TYPE"), Some(&b"text/html"[..]));
+        assert_eq!(result.header("accept"), None);
+
+        let input = b"HTTP/1.1 200 OK\r\ncontent-type:text/html\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.header("Content-Type"), Some(&b"text/html"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_header_all() {
+        let input = b"HTTP/1.1 200 OK\r\nSet-Cookie:a=1\r\nset-cookie:b=2\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(
+            result.header_all("Set-Cookie"),
+            vec![&b"a=1"[..], &b"b=2"[..]]
+        );
+        assert_eq!(result.header("set-cookie"), Some(&b"a=1"[..]));
+        assert!(result.header_all("Host").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_header_value_whitespace() {
+        let input = b"GET / HTTP/1.1\r\nHost:  example.com \r\nAccept:\t*/*\t\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.header("Host"), Some(&b"example.com"[..]));
+        assert_eq!(result.header("Accept"), Some(&b"*/*"[..]));
+        assert_eq!(result.headers.get("Host"), Some(&b"example.com"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_status_code_u16() {
+        let result = parse_response(b"HTTP/1.1 404 Not Found\r\n\r\n").unwrap();
+        assert_eq!(result.status_code_u16(), Some(404));
+        let mut result = parse_response(b"HTTP/1.1 100 Continue\r\n\r\n").unwrap();
+        assert_eq!(result.status_code_u16(), Some(100));
+        // Parsing rejects these, but the field is public and may be set
+        // afterwards.
+        result.status_code = b"2x0";
+        assert_eq!(result.status_code_u16(), None);
+        result.status_code = b"2000";
+        assert_eq!(result.status_code_u16(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_method_enum() {
+        let result = parse_request(b"DELETE /item HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.method_enum(), Method::Delete);
+        let result = parse_request(b"BREW /pot HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.method_enum(), Method::Other(b"BREW".to_vec()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_partial() {
+        let input = b"POST / HTTP/1.1\r\nHost:x\r\nContent-Length:3\r\n\r\nabc";
+        for end in 0..input.len() {
+            assert!(parse_request_partial(&input[..end]).unwrap().is_none());
+        }
+        let result = parse_request_partial(input).unwrap().unwrap();
+        assert_eq!(result.body, b"abc");
+
+        let input = b"HTTP/1.1 200 OK\r\nHost";
+        assert!(parse_response_partial(input).unwrap().is_none());
+        let input = b"HTTP/1.1 200 OK\r\n\r\n";
+        assert!(parse_response_partial(input).unwrap().is_some());
+        let input = b"HTTP/1.1\r\n\r\n";
+        assert_eq!(
+            parse_response_partial(input).err(),
+            Some(ParseError::MissingStatusLine)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_partial_chunked() {
+        let input = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n";
+        for end in 0..input.len() {
+            assert!(parse_request_partial(&input[..end]).unwrap().is_none());
+        }
+        assert!(parse_request_partial(
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nab"
+        )
+        .unwrap()
+        .is_none());
+        let result = parse_request_partial(input).unwrap().unwrap();
+        assert_eq!(result.body, b"3\r\nabc\r\n0\r\n\r\n");
+
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n";
+        assert!(parse_response_partial(input).unwrap().is_none());
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n";
+        assert!(parse_response_partial(input).unwrap().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_with_len() {
+        let first = b"POST /a HTTP/1.1\r\nContent-Length:3\r\n\r\nabc";
+        let mut input = first.to_vec();
+        input.extend_from_slice(b"GET /b HTTP/1.1\r\n\r\n");
+        let (result, len) = parse_request_with_len(&input).unwrap();
+        assert_eq!(result.body, b"abc");
+        assert_eq!(len, first.len());
+        let (result, _) = parse_request_with_len(&input[len..]).unwrap();
+        assert_eq!(result.url, b"/b");
+
+        let input = b"GET /a HTTP/1.1\r\nHost: x\r\n\r\nGET /b HTTP/1.1\r\n\r\n";
+        let (result, len) = parse_request_with_len(input).unwrap();
+        assert_eq!(result.body, b"");
+        assert_eq!(len, 28);
+        let (result, len) = parse_request_with_len(&input[len..]).unwrap();
+        assert_eq!(result.url, b"/b");
+        assert_eq!(len, 19);
+
+        let input = b"HTTP/1.1 200 OK\r\n\r\nrest";
+        let (_, len) = parse_response_with_len(input).unwrap();
+        assert_eq!(len, input.len());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_headers_ordered() {
+        let input = b"GET / HTTP/1.1\r\nZ:1\r\nA:2\r\nM:3\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        let headers: Vec<_> = result.headers_ordered().collect();
+        assert_eq!(
+            headers,
+            vec![
+                (&b"Z"[..], &b"1"[..]),
+                (&b"A"[..], &b"2"[..]),
+                (&b"M"[..], &b"3"[..])
+            ]
+        );
+
+        let input = b"HTTP/1.1 200 OK\r\nServer:x\r\nDate:y\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(
+            result.headers_ordered().next(),
+            Some((&b"Server"[..], &b"x"[..]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_header_value_with_colon() {
+        let input = b"HTTP/1.1 302 Found\r\nLocation:http://example.com/x\r\nDate: Mon, 01 Jan 2024 00:00:00 GMT\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(
+            result.header("Location"),
+            Some(&b"http://example.com/x"[..])
+        );
+        assert_eq!(
+            result.header("Date"),
+            Some(&b"Mon, 01 Jan 2024 00:00:00 GMT"[..])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_empty_reason_phrase() {
+        let result = parse_response(b"HTTP/1.0 200\r\nServer:x\r\n\r\n").unwrap();
+        assert_eq!(result.status_code, b"200");
+        assert_eq!(result.status, b"");
+        assert_eq!(result.header("Server"), Some(&b"x"[..]));
+
+        let result = parse_response(b"HTTP/1.0 204 \r\n\r\n").unwrap();
+        assert_eq!(result.status_code, b"204");
+        assert_eq!(result.status, b"");
+    }
+
+    #[test]
+    fn test_parse_raw() {
+        let input = b"GET /index HTTP/1.1\r\nHost:x\r\nContent-Length:3\r\n\r\nabcdef";
+        let mut headers = [EMPTY_HEADER; 4];
+        let result = parse_request_raw(input, &mut headers).unwrap();
+        assert_eq!(result.method, b"GET");
+        assert_eq!(result.url, b"/index");
+        assert_eq!(result.http_version, b"HTTP/1.1");
+        assert_eq!(
+            result.headers,
+            &[
+                (&b"Host"[..], &b"x"[..]),
+                (&b"Content-Length"[..], &b"3"[..])
+            ]
+        );
+        assert_eq!(result.body, b"abc");
+
+        let mut headers = [EMPTY_HEADER; 1];
+        assert_eq!(
+            parse_request_raw(input, &mut headers).err(),
+            Some(ParseError::TooManyHeaders)
+        );
+
+        let input = b"HTTP/1.1 404 Not Found\r\nServer:x\r\n\r\n";
+        let mut headers = [EMPTY_HEADER; 1];
+        let result = parse_response_raw(input, &mut headers).unwrap();
+        assert_eq!(result.status_code, b"404");
+        assert_eq!(result.status, b"Not Found");
+        assert_eq!(result.headers, &[(&b"Server"[..], &b"x"[..])]);
+    }
+
+    /// Runs without the `std` feature too, covering the no_std API.
+    #[test]
+    fn test_parse_raw_framing() {
+        let input =
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\nGET";
+        let mut headers = [EMPTY_HEADER; 2];
+        let result = parse_request_raw(input, &mut headers).unwrap();
+        assert_eq!(result.headers.len(), 1);
+        assert_eq!(result.body, b"3\r\nabc\r\n0\r\n\r\n");
+        assert!(is_chunked_complete(result.body));
+
+        let input = b"GET / HTTP/1.1\r\nContent-Length: 5\r\n\r\nab";
+        assert_eq!(
+            parse_request_raw(input, &mut headers).err(),
+            Some(ParseError::IncompleteBody {
+                expected: 5,
+                got: 2
+            })
+        );
+        assert_eq!(
+            parse_request_raw(b"\r\n", &mut headers).err(),
+            Some(ParseError::Empty)
+        );
+
+        let input = b"HTTP/1.1 204 No Content\r\n\r\n";
+        let result = parse_response_raw(input, &mut headers).unwrap();
+        assert_eq!(result.status_code, b"204");
+        assert!(result.headers.is_empty());
+        assert_eq!(result.body, b"");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_request_into() {
+        let input = b"POST /a HTTP/1.1\r\nHost:x\r\nContent-Length:3\r\n\r\nabcdef";
+        let mut headers = Vec::with_capacity(8);
+        let result = parse_request_into(input, &mut headers).unwrap();
+        assert_eq!(result.method, b"POST");
+        assert_eq!(result.url, b"/a");
+        assert_eq!(result.headers.len(), 2);
+        assert_eq!(result.headers[0], (&b"Host"[..], &b"x"[..]));
+        assert_eq!(result.body, b"abc");
+
+        let capacity = headers.capacity();
+        let result = parse_request_into(b"GET /b HTTP/1.1\r\nA:1\r\n\r\n", &mut headers).unwrap();
+        assert_eq!(result.url, b"/b");
+        assert_eq!(result.headers, &[(&b"A"[..], &b"1"[..])]);
+        assert_eq!(headers.capacity(), capacity);
+
+        assert_eq!(
+            parse_request_into(b"GET / HTTP/1.1\r\nA:1\r\n", &mut headers).err(),
+            Some(ParseError::TruncatedInput)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_with_partial() {
+        let config = ParserConfig {
+            tolerant: true,
+            ..ParserConfig::default()
+        };
+        let err = parse_request_with_partial(b"GET /index HTPP/1\r\nHost: x\r\n\r\n", &config)
+            .err()
+            .unwrap();
+        assert_eq!(err.error, ParseError::InvalidVersion);
+        assert_eq!(err.method, Some(&b"GET"[..]));
+        assert_eq!(err.url, Some(&b"/index"[..]));
+        assert_eq!(err.to_string(), "invalid http version");
+
+        let err = parse_request_with_partial(b"POST /a", &config)
+            .err()
+            .unwrap();
+        assert_eq!(ParseError::from(err), ParseError::TruncatedInput);
+        assert_eq!(err.method, Some(&b"POST"[..]));
+        assert_eq!(err.url, None);
+
+        let result = parse_request_with_partial(b"GET / HTTP/1.1\r\n\r\n", &config).unwrap();
+        assert_eq!(result.url, b"/");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_version() {
+        let result = parse_request(b"GET / HTTP/1.0\r\n\r\n").unwrap();
+        assert_eq!(result.version(), Some((1, 0)));
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert_eq!(result.version(), Some((1, 1)));
+
+        assert_eq!(
+            parse_request(b"GET / FOO\n\r\n").err(),
+            Some(ParseError::InvalidVersion)
+        );
+        assert_eq!(
+            parse_request(b"GET / HTTP/1.x\n\r\n").err(),
+            Some(ParseError::InvalidVersion)
+        );
+        assert_eq!(
+            parse_response(b"HTTP/11 200 OK\r\n\r\n").err(),
+            Some(ParseError::InvalidVersion)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_with_config() {
+        let config = ParserConfig {
+            max_headers: 2,
+            max_header_bytes: 8,
+            ..ParserConfig::default()
+        };
+        let input = b"GET / HTTP/1.1\r\nA:1\r\nB:2\r\n\r\n";
+        assert!(parse_request_with_config(input, &config).is_ok());
+
+        let input = b"GET / HTTP/1.1\r\nA:1\r\nB:2\r\nC:3\r\n\r\n";
+        assert_eq!(
+            parse_request_with_config(input, &config).err(),
+            Some(ParseError::TooManyHeaders)
+        );
+
+        let input = b"HTTP/1.1 200 OK\r\nA:12345678\r\n\r\n";
+        assert_eq!(
+            parse_response_with_config(input, &config).err(),
+            Some(ParseError::HeaderTooLong)
+        );
+        let input = b"HTTP/1.1 200 OK\r\nA:123456\r\n\r\n";
+        assert!(parse_response_with_config(input, &config).is_ok());
+        let input = b"HTTP/1.1 200 OK\r\nABCDEFGH:1\r\n\r\n";
+        assert_eq!(
+            parse_response_with_config(input, &config).err(),
+            Some(ParseError::HeaderTooLong)
+        );
+        let input = b"HTTP/1.1 200 OK\r\nABCDEF:\r\n\r\n";
+        assert!(parse_response_with_config(input, &config).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_transfer_encoding_and_content_length() {
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert!(result.is_smuggling_suspect());
+        assert_eq!(result.body, b"5\r\nhello\r\n0\r\n\r\n");
+        assert_eq!(result.decoded_body(), b"hello");
+
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert!(!result.is_smuggling_suspect());
+        assert_eq!(result.decoded_body(), b"hello");
+
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nhello";
+        let result = parse_response(input).unwrap();
+        assert!(!result.is_smuggling_suspect());
+        assert_eq!(result.body, b"hel");
+
+        let input =
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 0\r\n\r\n0\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert!(result.is_smuggling_suspect());
+        assert_eq!(result.body, b"0\r\n\r\n");
+
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: gzip,\r\n chunked\r\n\r\n0\r\n\r\nnext";
+        assert_eq!(parse_response(input).unwrap().body, b"0\r\n\r\n");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_chunked_body_framing() {
+        let message = b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\nX-T: 1\r\n\r\n";
+        let mut input = message.to_vec();
+        input.extend_from_slice(b"GET /b HTTP/1.1\r\n\r\n");
+        let (result, len) = parse_request_with_len(&input).unwrap();
+        assert_eq!(result.body, b"3\r\nabc\r\n0\r\nX-T: 1\r\n\r\n");
+        assert_eq!(result.wire_len(), message.len());
+        assert_eq!(len, message.len());
+
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nab";
+        assert_eq!(
+            parse_response(input).err(),
+            Some(ParseError::TruncatedInput)
+        );
+        let mut headers = [EMPTY_HEADER; 1];
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\nextra";
+        let result = parse_response_raw(input, &mut headers).unwrap();
+        assert_eq!(result.body, b"0\r\n\r\n");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_body_str() {
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\nh\xc3\xa9llo").unwrap();
+        assert_eq!(result.body_str(), Ok("h\u{e9}llo"));
+        assert_eq!(result.body_str_lossy(), "h\u{e9}llo");
+
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\nbad\xff").unwrap();
+        assert!(result.body_str().is_err());
+        assert_eq!(result.body_str_lossy(), "bad\u{fffd}");
+
+        let result = parse_request(b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\nok").unwrap();
+        assert_eq!(result.body_str(), Ok("ok"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_lf_only_terminator() {
+        let result = parse_request(b"GET / HTTP/1.1\nhost:x\nContent-Length:4\n\nbody").unwrap();
+        assert_eq!(result.header("host"), Some(&b"x"[..]));
+        assert_eq!(result.body, b"body");
+        let result = parse_request(b"GET / HTTP/1.1\n\n").unwrap();
+        assert!(result.headers.is_empty());
+        assert_eq!(result.body, b"");
+        let result = parse_response(b"HTTP/1.1 200 OK\nA: 1\nB: 2\n\nok").unwrap();
+        assert_eq!(result.header("B"), Some(&b"2"[..]));
+        assert_eq!(result.body, b"ok");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_invalid_status_code() {
+        for input in [
+            &b"HTTP/1.1 99 Odd\r\n\r\n"[..],
+            b"HTTP/1.1 2000 Odd\r\n\r\n",
+            b"HTTP/1.1 2x0 Odd\r\n\r\n",
+        ] {
+            assert_eq!(
+                parse_response(input).err(),
+                Some(ParseError::InvalidStatusCode)
+            );
+            let mut headers = [EMPTY_HEADER; 4];
+            assert_eq!(
+                parse_response_raw(input, &mut headers).err(),
+                Some(ParseError::InvalidStatusCode)
+            );
+        }
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n\r\n").is_ok());
+        assert_eq!(
+            parse_response(b"HTTP/1.1 \r\n\r\n").err(),
+            Some(ParseError::UnexpectedByte {
+                offset: 9,
+                byte: b'\r'
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_multi_word_reason() {
+        let result = parse_response(b"HTTP/1.1 404 Not Found\r\n\r\n").unwrap();
+        assert_eq!(result.status, b"Not Found");
+        let result = parse_response(b"HTTP/1.1 503 Service  Unavailable \t\r\n\r\n").unwrap();
+        assert_eq!(result.status, b"Service  Unavailable");
+        let result = parse_response(b"HTTP/1.1 200  \r\n\r\n").unwrap();
+        assert_eq!(result.status, b"");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_empty_header_value() {
+        let input = b"GET / HTTP/1.1\r\nX-Empty:\r\nHost:x\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.header("X-Empty"), Some(&b""[..]));
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+
+        let input = b"HTTP/1.1 200 OK\nX-Empty: \nServer:y\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.header("X-Empty"), Some(&b""[..]));
+        assert_eq!(result.header("Server"), Some(&b"y"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_max_request_line() {
+        let config = ParserConfig {
+            max_request_line: 14,
+            ..ParserConfig::default()
+        };
+        let input = b"GET / HTTP/1.1\r\n\r\n";
+        assert!(parse_request_with_config(input, &config).is_ok());
+        let input = b"GET /a HTTP/1.1\r\n\r\n";
+        assert_eq!(
+            parse_request_with_config(input, &config).err(),
+            Some(ParseError::RequestLineTooLong)
+        );
+
+        // The limit applies before the line ending arrives.
+        let mut input = b"GET /".to_vec();
+        input.resize(64 * 1024, b'a');
+        assert_eq!(
+            parse_request(&input).err(),
+            Some(ParseError::RequestLineTooLong)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_tolerant_headers() {
+        let input = b"GET / HTTP/1.1\r\nHost: x\r\nbroken line\r\nAccept: */*\r\n\r\n";
+        assert_eq!(
+            parse_request(input).err(),
+            Some(ParseError::MalformedHeader)
+        );
+
+        let config = ParserConfig {
+            tolerant: true,
+            ..ParserConfig::default()
+        };
+        let result = parse_request_with_config(input, &config).unwrap();
+        assert_eq!(result.headers.len(), 2);
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+        assert_eq!(result.header("Accept"), Some(&b"*/*"[..]));
+
+        let input = b"HTTP/1.1 200 OK\nServer: y\nnocolon\nContent-Length: 2\n\nok";
+        let result = parse_response_with_config(input, &config).unwrap();
+        assert_eq!(result.headers.len(), 2);
+        assert_eq!(result.body, b"ok");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_invalid_header_name() {
+        for input in [
+            &b"GET / HTTP/1.1\r\nX-\0Evil: 1\r\nHost: x\r\n\r\n"[..],
+            b"GET / HTTP/1.1\r\nX Evil: 1\r\nHost: x\r\n\r\n",
+            b"GET / HTTP/1.1\r\nHost : x\r\n\r\n",
+            b"GET / HTTP/1.1\r\n: x\r\n\r\n",
+            b"GET / HTTP/1.1\r\nX(y): x\r\n\r\n",
+        ] {
+            assert_eq!(
+                parse_request(input).err(),
+                Some(ParseError::InvalidHeaderName)
+            );
+        }
+
+        let config = ParserConfig {
+            tolerant: true,
+            ..ParserConfig::default()
+        };
+        let input = b"GET / HTTP/1.1\r\nX-\0Evil: 1\r\nX Evil: 2\r\nHost: x\r\n\r\n";
+        let result = parse_request_with_config(input, &config).unwrap();
+        assert_eq!(result.headers.len(), 1);
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+
+        let result = parse_request(b"GET / HTTP/1.1\r\n!#$%&'*+-.^_`|~09az: ok\r\n\r\n");
+        assert_eq!(result.unwrap().headers.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_bare_cr_in_header() {
+        let input = b"GET / HTTP/1.1\r\nX-Odd: foo\rbar\r\nHost: x\r\n\r\n";
+        let err = parse_request(input).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::UnexpectedByte {
+                offset: 26,
+                byte: b'\r'
+            }
+        );
+        assert_eq!(err.to_string(), "unexpected byte 0x0d at offset 26");
+
+        let config = ParserConfig {
+            tolerant: true,
+            ..ParserConfig::default()
+        };
+        let result = parse_request_with_config(input, &config).unwrap();
+        assert_eq!(result.header("X-Odd"), Some(&b"foo\rbar"[..]));
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+
+        // A CR at the start of a line only ends the headers before an LF.
+        let input = b"HTTP/1.1 200 OK\r\nA: 1\r\n\rB: 2\r\n\r\nok";
+        let result = parse_response_with_config(input, &config).unwrap();
+        // The CR makes the name invalid, so tolerant mode drops the line.
+        assert_eq!(result.headers.len(), 1);
+        assert_eq!(result.body, b"ok");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_allow_tab_separators() {
+        let input = b"GET\t/index\tHTTP/1.1\r\nHost: x\r\n\r\n";
+        assert_eq!(
+            parse_request(input).err(),
+            Some(ParseError::MalformedRequestLine)
+        );
+
+        let config = ParserConfig {
+            allow_tab_separators: true,
+            ..ParserConfig::default()
+        };
+        let result = parse_request_with_config(input, &config).unwrap();
+        assert_eq!(result.method, b"GET");
+        assert_eq!(result.url, b"/index");
+        assert_eq!(result.http_version, b"HTTP/1.1");
+        assert_eq!(
+            parse_request_with_config(b"GET\t\t/ HTTP/1.1\r\n\r\n", &config).err(),
+            Some(ParseError::MalformedRequestLine)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_empty_body() {
+        let result = parse_request(b"GET / HTTP/1.1\r\nhost:x\r\n\r\n").unwrap();
+        assert_eq!(result.body, b"");
+        let result = parse_request(b"GET / HTTP/1.1\nhost:x\n\r\n").unwrap();
+        assert_eq!(result.body, b"");
+        let result = parse_request(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.body, b"");
+        let result =
+            parse_response(b"HTTP/1.1 204 No Content\r\nContent-Length:0\r\n\r\n").unwrap();
+        assert_eq!(result.body, b"");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_obs_fold() {
+        let input = b"GET / HTTP/1.1\r\nX-Long: part1\r\n \t part2\r\nHost:x\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.header("X-Long"), Some(&b"part1 part2"[..]));
+        assert_eq!(result.header_all("x-long"), [&b"part1 part2"[..]]);
+        assert!(result
+            .raw_headers()
+            .starts_with(b"X-Long: part1\r\n \t part2\r\n"));
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+        assert_eq!(result.headers.len(), 2);
+
+        let input = b"HTTP/1.1 200 OK\nX-Long:a\n b\n c\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.header("X-Long"), Some(&b"a b c"[..]));
+
+        let input = b"GET / HTTP/1.1\r\n folded:x\r\n\r\n";
+        assert_eq!(
+            parse_request(input).err(),
+            Some(ParseError::UnexpectedByte {
+                offset: 16,
+                byte: b' '
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_headers_end() {
+        let input = b"POST / HTTP/1.1\r\nHost: x\r\nContent-Length: 4\r\n\r\nbody";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.headers_end(), input.len() - 4);
+        assert_eq!(&input[..result.headers_end()], &input[..input.len() - 4]);
+        assert!(input[..result.headers_end()].ends_with(b"\r\n\r\n"));
+
+        let input = b"HTTP/1.1 200 OK\n\nok";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.headers_end(), 17);
+        assert_eq!(&input[result.headers_end()..], b"ok");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_wire_len() {
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nbodyGET / HTTP/1.1\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.wire_len(), 42);
+        assert_eq!(result.wire_len(), parse_request_with_len(input).unwrap().1);
+
+        let input = b"HTTP/1.1 204 No Content\r\n\r\n";
+        assert_eq!(parse_response(input).unwrap().wire_len(), input.len());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_response_ends_after_headers() {
+        let input = b"HTTP/1.1 200 OK\r\nServer: x\r\nConnection: close\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.header("Connection"), Some(&b"close"[..]));
+        assert_eq!(result.body, b"");
+        assert_eq!(result.wire_len(), input.len());
+        let mut headers = [EMPTY_HEADER; 4];
+        assert_eq!(
+            parse_response_raw(input, &mut headers)
+                .unwrap()
+                .headers
+                .len(),
+            2
+        );
+
+        let result = parse_response(b"HTTP/1.1 200 OK\n").unwrap();
+        assert!(result.headers.is_empty());
+
+        // Still incomplete: partial input may be followed by more headers,
+        // a header line cut short is never complete, and a declared body
+        // must still arrive in full.
+        assert_eq!(parse_response_partial(input).unwrap().map(|r| r.body), None);
+        assert_eq!(
+            parse_response(b"HTTP/1.1 200 OK\r\nServer: x").err(),
+            Some(ParseError::TruncatedInput)
+        );
+        assert_eq!(
+            parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n").err(),
+            Some(ParseError::IncompleteBody {
+                expected: 2,
+                got: 0
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_expects_continue() {
+        let result = parse_request(b"PUT /f HTTP/1.1\r\nExpect: 100-Continue\r\n\r\n").unwrap();
+        assert!(result.expects_continue());
+        let result = parse_request(b"PUT /f HTTP/1.1\r\nExpect: nothing\r\n\r\n").unwrap();
+        assert!(!result.expects_continue());
+        let result = parse_request(b"PUT /f HTTP/1.1\r\n\r\n").unwrap();
+        assert!(!result.expects_continue());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_response_trailers() {
+        let input = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5\r\nhello\r\n0\r\nX-Checksum: 5d41402a\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(result.decoded_body(), b"hello");
+        assert_eq!(result.trailers().get("X-Checksum"), Some(&b"5d41402a"[..]));
+        let result = parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+        assert!(result.trailers().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_try_from() {
+        use core::convert::TryInto;
+
+        let input = &b"GET /a HTTP/1.1\r\nHost: x\r\n\r\n"[..];
+        let result = Request::try_from(input).unwrap();
+        assert_eq!(result.url, b"/a");
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+        assert_eq!(
+            Request::try_from(&b"GET / FOO\r\n\r\n"[..]).err(),
+            Some(ParseError::InvalidVersion)
+        );
+
+        let result: Response = (&b"HTTP/1.1 404 Not Found\r\n\r\n"[..]).try_into().unwrap();
+        assert_eq!(result.status_code, b"404");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_body_start_follows_terminator() {
+        // The body starts right after the blank line's LF, whether or not
+        // the header lines and the blank line end with a CR.
+        for input in [
+            &b"POST / HTTP/1.1\nContent-Length: 3\n\nabc"[..],
+            b"POST / HTTP/1.1\r\nContent-Length: 3\r\n\nabc",
+            b"POST / HTTP/1.1\nContent-Length: 3\n\r\nabc",
+        ] {
+            let result = parse_request(input).unwrap();
+            assert_eq!(result.body, b"abc");
+            assert_eq!(result.headers_end(), input.len() - 3);
+        }
+        let result = parse_response(b"HTTP/1.1 200 OK\nServer: x\n\n\nrest").unwrap();
+        assert_eq!(result.body, b"\nrest");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_has_body() {
+        let result = parse_request(b"GET / HTTP/1.1\r\n\r\nleftover").unwrap();
+        assert!(!result.has_body());
+        assert_eq!(result.body, b"");
+        assert_eq!(result.wire_len(), 18);
+        let result = parse_request(b"POST / HTTP/1.1\r\nContent-Length: 0\r\n\r\n").unwrap();
+        assert!(result.has_body());
+        let input = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n";
+        assert!(parse_request(input).unwrap().has_body());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_response_for_method() {
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHTTP/1.1 404 Not Found\r\n\r\n";
+        let result = parse_response_for_method(input, Method::Head).unwrap();
+        assert_eq!(result.header("Content-Length"), Some(&b"5"[..]));
+        assert_eq!(result.body, b"");
+        assert_eq!(
+            parse_response_for_method(input, Method::Get).unwrap().body,
+            b"HTTP/"
+        );
+
+        for status in ["100 Continue", "204 No Content", "304 Not Modified"] {
+            let input = format!("HTTP/1.1 {}\r\nContent-Length: 3\r\n\r\nabc", status);
+            let result = parse_response_for_method(input.as_bytes(), Method::Get).unwrap();
+            assert_eq!(result.body, b"", "{}", status);
+        }
+
+        let input = b"HTTP/1.1 200 Connection Established\r\n\r\n\x16\x03\x01";
+        let result = parse_response_for_method(input, Method::Connect).unwrap();
+        assert_eq!(result.body, b"");
+        let input = b"HTTP/1.1 407 Proxy Authentication Required\r\nContent-Length: 2\r\n\r\nno";
+        let result = parse_response_for_method(input, Method::Connect).unwrap();
+        assert_eq!(result.body, b"no");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_response_skipping_informational() {
+        let input = b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 103 Early Hints\r\n\
+            Link: </a.css>\r\n\r\nHTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nhi";
+        let (interim, response) = parse_response_skipping_informational(input).unwrap();
+        assert_eq!(interim.len(), 2);
+        assert_eq!(interim[0].status_code, b"100");
+        assert!(interim[0].body.is_empty());
+        assert_eq!(interim[1].header("link"), Some(&b"</a.css>"[..]));
+        assert!(interim[1].body.is_empty());
+        assert_eq!(response.status_code, b"200");
+        assert_eq!(response.body, b"hi");
+
+        let input = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\nframes";
+        let (interim, response) = parse_response_skipping_informational(input).unwrap();
+        assert!(interim.is_empty());
+        assert_eq!(response.status_code, b"101");
+
+        for input in [
+            &b"HTTP/1.1 100 Continue\r\n\r\n"[..],
+            b"HTTP/1.1 100 Continue\r\n\r\n\r\n",
+        ] {
+            assert_eq!(
+                parse_response_skipping_informational(input).err(),
+                Some(ParseError::TruncatedInput)
+            );
+        }
+        assert_eq!(
+            parse_response_skipping_informational(b"").err(),
+            Some(ParseError::Empty)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_raw_headers() {
+        let input = b"GET / HTTP/1.1\r\nhost:  x\r\nAccept: */*\r\n\r\nbody";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.raw_headers(), b"host:  x\r\nAccept: */*\r\n");
+
+        let result = parse_response(b"HTTP/1.1 200 OK\nServer:x\n\r\n").unwrap();
+        assert_eq!(result.raw_headers(), b"Server:x\n");
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert_eq!(result.raw_headers(), b"");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_malformed_request_line() {
+        for input in [
+            &b"GET\n\r\n"[..],
+            b"GET /\r\n\r\n",
+            b"GET / \r\n\r\n",
+            b" / HTTP/1.1\r\n\r\n",
+            b"GET  HTTP/1.1\r\n\r\n",
+        ] {
+            assert_eq!(
+                parse_request(input).err(),
+                Some(ParseError::MalformedRequestLine)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_keep_alive() {
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert!(result.keep_alive());
+        let result = parse_response(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n").unwrap();
+        assert!(!result.keep_alive());
+        let result = parse_response(b"HTTP/1.0 200 OK\r\n\r\n").unwrap();
+        assert!(!result.keep_alive());
+        let result = parse_response(b"HTTP/1.0 200 OK\r\nConnection: Keep-Alive\r\n\r\n").unwrap();
+        assert!(result.keep_alive());
+
+        let result = parse_request(b"GET / HTTP/1.1\r\nConnection: TE, close\r\n\r\n").unwrap();
+        assert!(!result.keep_alive());
+        let result = parse_request(b"GET / HTTP/1.1\r\nConnection: upgrade\r\n\r\n").unwrap();
+        assert!(result.keep_alive());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_requests() {
+        let input = b"POST /a HTTP/1.1\r\nContent-Length:5\r\n\r\nhelloGET /b HTTP/1.1\r\nHost:x\r\n\r\nGET /c HTTP/1.1\r\n\r\n";
+        let results = parse_requests(input).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].url, b"/a");
+        assert_eq!(results[0].body, b"hello");
+        assert_eq!(results[1].url, b"/b");
+        assert_eq!(results[1].body, b"");
+        assert_eq!(results[2].url, b"/c");
+
+        assert!(parse_requests(b"").unwrap().is_empty());
+        assert_eq!(
+            parse_requests(b"GET /a HTTP/1.1\r\n\r\nGET /b").err(),
+            Some(ParseError::TruncatedInput)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_requests_chunked() {
+        let input = b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5\r\nhello\r\n0\r\n\r\nGET /b HTTP/1.1\r\n\r\n";
+        let results = parse_requests(input).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].url, b"/a");
+        assert_eq!(results[0].body, b"5\r\nhello\r\n0\r\n\r\n");
+        assert_eq!(results[1].url, b"/b");
+        assert_eq!(results[1].body, b"");
+    }
+
+    /// A small xorshift generator so the fuzz test needs no dependencies.
+    #[cfg(feature = "std")]
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_random_input() {
+        let valid: &[&[u8]] = &[
+            b"GET /index HTTP/1.1\r\nHost: x\r\nContent-Length: 3\r\n\r\nabc",
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n",
+        ];
+        let alphabet = b" :\r\n\t/HTTP1.0GET";
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..5000 {
+            let mut input = match xorshift(&mut state) % 3 {
+                0 => (0..xorshift(&mut state) % 64)
+                    .map(|_| xorshift(&mut state) as u8)
+                    .collect(),
+                1 => (0..xorshift(&mut state) % 64)
+                    .map(|_| alphabet[xorshift(&mut state) as usize % alphabet.len()])
+                    .collect(),
+                _ => valid[xorshift(&mut state) as usize % valid.len()].to_vec(),
+            };
+            for _ in 0..xorshift(&mut state) % 4 {
+                if !input.is_empty() {
+                    let at = xorshift(&mut state) as usize % input.len();
+                    input[at] = xorshift(&mut state) as u8;
+                }
+            }
+            let _ = parse_request(&input);
+            let _ = parse_requests(&input);
+            let mut headers = [EMPTY_HEADER; 8];
+            let _ = parse_request_raw(&input, &mut headers);
+            if let Ok(response) = parse_response(&input) {
+                let _ = response.decoded_body();
+            }
+            let mut headers = [EMPTY_HEADER; 8];
+            let _ = parse_response_raw(&input, &mut headers);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_request_iter() {
+        let input = b"GET /a HTTP/1.1\r\n\r\nPOST /b HTTP/1.1\r\nContent-Length: 2\r\n\r\nhiGET /c HTTP/1.1\r\nHo";
+        let mut requests = RequestIter::new(input);
+        assert_eq!(requests.next().unwrap().unwrap().url, b"/a");
+        let request = requests.next().unwrap().unwrap();
+        assert_eq!(request.url, b"/b");
+        assert_eq!(request.body, b"hi");
+        assert_eq!(requests.remaining(), b"GET /c HTTP/1.1\r\nHo");
+        assert_eq!(
+            requests.next().unwrap().err(),
+            Some(ParseError::TruncatedInput)
+        );
+        assert!(requests.next().is_none());
+
+        let input = b"GET /a HTTP/1.1\r\n\r\nGET /b HTTP/1.1\r\n\r\n";
+        let urls: Vec<_> = RequestIter::new(input)
+            .map(|request| request.unwrap().url)
+            .collect();
+        assert_eq!(urls, [&b"/a"[..], b"/b"]);
+        assert!(RequestIter::new(b"").next().is_none());
+
+        let input = b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            2\r\nhi\r\n0\r\n\r\nGET /b HTTP/1.1\r\n\r\nPOST /c HTTP/1.1\r\n\
+            Transfer-Encoding: chunked\r\n\r\n2\r\nhi";
+        let mut requests = RequestIter::new(input);
+        let request = requests.next().unwrap().unwrap();
+        assert_eq!(request.body, b"2\r\nhi\r\n0\r\n\r\n");
+        assert_eq!(requests.next().unwrap().unwrap().url, b"/b");
+        assert_eq!(
+            requests.next().unwrap().err(),
+            Some(ParseError::TruncatedInput)
+        );
+        assert!(requests.next().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_parse_leading_empty_lines() {
+        let input = b"\r\n\r\n\nGET / HTTP/1.1\r\nHost:x\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        assert_eq!(result.method, b"GET");
+        assert_eq!(result.url, b"/");
+        assert_eq!(result.header("Host"), Some(&b"x"[..]));
+        assert_eq!(parse_request(b"\r\n\r\n").err(), Some(ParseError::Empty));
     }
 }
@@ -0,0 +1,202 @@
+use crate::{trim_whitespace, HeaderSink, Request, Response};
+
+/// A parsed `Content-Type` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaType<'a> {
+    /// The `type/subtype` part, such as `text/html`.
+    pub mime: &'a [u8],
+    /// The `charset` parameter, without quotes.
+    pub charset: Option<&'a [u8]>,
+}
+
+impl<'a> MediaType<'a> {
+    /// Parses a media type with its parameters. Parameters other than
+    /// `charset` are ignored.
+    pub fn parse(value: &'a [u8]) -> Option<MediaType<'a>> {
+        let mut parts = value.split(|b| *b == b';');
+        let mime = trim_whitespace(parts.next()?);
+        if mime.is_empty() {
+            return None;
+        }
+        let charset = parameters(parts)
+            .find(|(name, _)| name.eq_ignore_ascii_case(b"charset"))
+            .map(|(_, value)| value);
+        Some(MediaType { mime, charset })
+    }
+}
+
+/// One entry of an `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaRange<'a> {
+    /// The `type/subtype` part, which may contain `*` wildcards.
+    pub mime: &'a [u8],
+    /// The `q` parameter, or `1.0` when it is absent or invalid.
+    pub quality: f32,
+}
+
+/// Splits `;`-separated `name=value` parameters, trimming whitespace and
+/// the quotes around quoted values. Parameters without `=` are skipped.
+pub(crate) fn parameters<'a>(
+    parts: impl Iterator<Item = &'a [u8]>,
+) -> impl Iterator<Item = (&'a [u8], &'a [u8])> {
+    parts.filter_map(|param| {
+        let eq = param.iter().position(|b| *b == b'=')?;
+        let name = trim_whitespace(&param[..eq]);
+        let value = trim_whitespace(&param[eq + 1..]);
+        let value = match value {
+            [b'"', inner @ .., b'"'] => inner,
+            value => value,
+        };
+        Some((name, value))
+    })
+}
+
+/// Parses a `q` parameter value, which must lie between 0 and 1.
+pub(crate) fn quality(value: &[u8]) -> Option<f32> {
+    let quality: f32 = core::str::from_utf8(value).ok()?.parse().ok()?;
+    (0.0..=1.0).contains(&quality).then_some(quality)
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Parses the `Content-Type` header.
+    pub fn content_type(&self) -> Option<MediaType<'_>> {
+        MediaType::parse(self.header("content-type")?)
+    }
+
+    /// Returns the `boundary` parameter of a `multipart/*` content type,
+    /// without quotes.
+    pub fn multipart_boundary(&self) -> Option<&[u8]> {
+        let mut parts = self.header("content-type")?.split(|b| *b == b';');
+        let mime = trim_whitespace(parts.next()?);
+        if !mime
+            .get(..10)
+            .is_some_and(|kind| kind.eq_ignore_ascii_case(b"multipart/"))
+        {
+            return None;
+        }
+        parameters(parts)
+            .find(|(name, _)| name.eq_ignore_ascii_case(b"boundary"))
+            .map(|(_, value)| value)
+            .filter(|boundary| !boundary.is_empty())
+    }
+
+    /// Parses the `Accept` headers into media ranges, most preferred first.
+    /// Ranges with equal quality keep the order they were listed in.
+    pub fn accept(&self) -> Vec<MediaRange<'_>> {
+        let mut ranges: Vec<_> = self
+            .header_all("accept")
+            .into_iter()
+            .flat_map(|value| value.split(|b| *b == b','))
+            .filter_map(|range| {
+                let mut parts = range.split(|b| *b == b';');
+                let mime = trim_whitespace(parts.next()?);
+                if mime.is_empty() {
+                    return None;
+                }
+                let quality = parameters(parts)
+                    .find(|(name, _)| name.eq_ignore_ascii_case(b"q"))
+                    .and_then(|(_, value)| quality(value))
+                    .unwrap_or(1.0);
+                Some(MediaRange { mime, quality })
+            })
+            .collect();
+        ranges.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+        ranges
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Parses the `Content-Type` header.
+    pub fn content_type(&self) -> Option<MediaType<'_>> {
+        MediaType::parse(self.header("content-type")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            MediaType::parse(b"text/html; charset=utf-8"),
+            Some(MediaType {
+                mime: b"text/html",
+                charset: Some(b"utf-8")
+            })
+        );
+        assert_eq!(
+            MediaType::parse(b" application/json "),
+            Some(MediaType {
+                mime: b"application/json",
+                charset: None
+            })
+        );
+        assert_eq!(
+            MediaType::parse(b"text/plain;format=flowed; Charset=\"ISO-8859-1\""),
+            Some(MediaType {
+                mime: b"text/plain",
+                charset: Some(b"ISO-8859-1")
+            })
+        );
+        assert_eq!(MediaType::parse(b" ; charset=utf-8"), None);
+    }
+
+    #[test]
+    fn test_content_type() {
+        let input = b"HTTP/1.1 200 OK\r\nContent-Type: text/html;charset=utf-8\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        let media_type = result.content_type().unwrap();
+        assert_eq!(media_type.mime, b"text/html");
+        assert_eq!(media_type.charset, Some(&b"utf-8"[..]));
+
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert_eq!(result.content_type(), None);
+    }
+
+    #[test]
+    fn test_multipart_boundary() {
+        let boundary = |content_type: &str| {
+            let input = format!("POST / HTTP/1.1\r\nContent-Type: {}\r\n\r\n", content_type);
+            parse_request(input.as_bytes())
+                .unwrap()
+                .multipart_boundary()
+                .map(<[u8]>::to_vec)
+        };
+        assert_eq!(
+            boundary("multipart/form-data; boundary=XYZ"),
+            Some(b"XYZ".to_vec())
+        );
+        assert_eq!(
+            boundary("Multipart/Form-Data;charset=utf-8; Boundary=\"a b:c\""),
+            Some(b"a b:c".to_vec())
+        );
+        assert_eq!(boundary("multipart/form-data"), None);
+        assert_eq!(boundary("multipart/form-data; boundary="), None);
+        assert_eq!(boundary("text/plain; boundary=XYZ"), None);
+    }
+
+    #[test]
+    fn test_accept() {
+        let input = b"GET / HTTP/1.1\r\nAccept: text/html;level=1 , application/json;q=0.9,\r\nAccept: */*; q=0.1, image/png;q=bad, text/plain;q=0.9\r\n\r\n";
+        let result = parse_request(input).unwrap();
+        let ranges = result.accept();
+        let mimes: Vec<_> = ranges.iter().map(|range| range.mime).collect();
+        assert_eq!(
+            mimes,
+            [
+                &b"text/html"[..],
+                b"image/png",
+                b"application/json",
+                b"text/plain",
+                b"*/*"
+            ]
+        );
+        assert_eq!(ranges[2].quality, 0.9);
+        assert_eq!(ranges[4].quality, 0.1);
+
+        let result = parse_request(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert!(result.accept().is_empty());
+    }
+}
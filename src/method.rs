@@ -0,0 +1,86 @@
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Head,
+    Options,
+    Patch,
+    Connect,
+    Trace,
+    /// Any other method, with the raw bytes preserved.
+    Other(Vec<u8>),
+}
+
+impl Method {
+    /// Whether the method is read-only by definition (RFC 7231 §4.2.1):
+    /// `GET`, `HEAD`, `OPTIONS` and `TRACE`.
+    pub fn is_safe(&self) -> bool {
+        matches!(
+            self,
+            Method::Get | Method::Head | Method::Options | Method::Trace
+        )
+    }
+
+    /// Whether repeating the request has the same effect as sending it once
+    /// (RFC 7231 §4.2.2), so a client may retry it after a failure: the
+    /// safe methods plus `PUT` and `DELETE`.
+    pub fn is_idempotent(&self) -> bool {
+        self.is_safe() || matches!(self, Method::Put | Method::Delete)
+    }
+}
+
+impl From<&[u8]> for Method {
+    /// Maps a request method token to its variant. Method names are
+    /// case-sensitive, so `b"get"` becomes `Method::Other`.
+    fn from(method: &[u8]) -> Method {
+        match method {
+            b"GET" => Method::Get,
+            b"POST" => Method::Post,
+            b"PUT" => Method::Put,
+            b"DELETE" => Method::Delete,
+            b"HEAD" => Method::Head,
+            b"OPTIONS" => Method::Options,
+            b"PATCH" => Method::Patch,
+            b"CONNECT" => Method::Connect,
+            b"TRACE" => Method::Trace,
+            other => Method::Other(other.to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes() {
+        assert_eq!(Method::from(&b"GET"[..]), Method::Get);
+        assert_eq!(Method::from(&b"PATCH"[..]), Method::Patch);
+        assert_eq!(Method::from(&b"get"[..]), Method::Other(b"get".to_vec()));
+        assert_eq!(
+            Method::from(&b"PROPFIND"[..]),
+            Method::Other(b"PROPFIND".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_is_safe_and_idempotent() {
+        for (method, safe, idempotent) in [
+            (Method::Get, true, true),
+            (Method::Head, true, true),
+            (Method::Options, true, true),
+            (Method::Trace, true, true),
+            (Method::Put, false, true),
+            (Method::Delete, false, true),
+            (Method::Post, false, false),
+            (Method::Patch, false, false),
+            (Method::Connect, false, false),
+            (Method::Other(b"PROPFIND".to_vec()), false, false),
+        ] {
+            assert_eq!(method.is_safe(), safe, "{:?}", method);
+            assert_eq!(method.is_idempotent(), idempotent, "{:?}", method);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{parse_headers, HeaderSink, Headers, ParserConfig};
+
+/// One part of a `multipart/*` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part<'a> {
+    pub headers: Headers<'a>,
+    pub body: &'a [u8],
+}
+
+/// Splits a multipart body on `--boundary` lines, stopping at the closing
+/// `--boundary--`. Anything before the first boundary is ignored, as are
+/// parts whose headers do not parse and a final part that is never closed.
+pub fn parse_multipart<'a>(body: &'a [u8], boundary: &[u8]) -> Vec<Part<'a>> {
+    let mut delimiter = b"--".to_vec();
+    delimiter.extend_from_slice(boundary);
+    let mut parts = vec![];
+    let mut next = find_delimiter(body, &delimiter, 0);
+    while let Some(at) = next {
+        let after = &body[at + delimiter.len()..];
+        if after.starts_with(b"--") {
+            break;
+        }
+        // Skip transport padding and the line break ending the boundary line.
+        let padding = after
+            .iter()
+            .take_while(|b| **b == b' ' || **b == b'\t')
+            .count();
+        let start = match &after[padding..] {
+            [b'\r', b'\n', ..] => padding + 2,
+            [b'\n', ..] => padding + 1,
+            _ => break,
+        };
+        let start = at + delimiter.len() + start;
+        next = find_delimiter(body, &delimiter, start);
+        let end = match next {
+            Some(end) if body[..end].ends_with(b"\r\n") => end - 2,
+            Some(end) => end - 1,
+            None => break,
+        };
+        if let Some(part) = parse_part(&body[start..end.max(start)]) {
+            parts.push(part);
+        }
+    }
+    parts
+}
+
+/// Finds the next delimiter at or after `from` that starts a line.
+fn find_delimiter(body: &[u8], delimiter: &[u8], from: usize) -> Option<usize> {
+    (from..body.len())
+        .find(|&i| body[i..].starts_with(delimiter) && (i == 0 || body[i - 1] == b'\n'))
+}
+
+fn parse_part(content: &[u8]) -> Option<Part<'_>> {
+    let mut headers = Headers::default();
+    let (_, body) = parse_headers(
+        content,
+        0,
+        &ParserConfig::default(),
+        false,
+        &mut |key, value| {
+            headers.insert(key, value);
+            Ok(())
+        },
+    )
+    .ok()?;
+    Some(Part {
+        headers,
+        body: &content[body..],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multipart() {
+        let body = b"preamble\r\n\
+            --XYZ\r\n\
+            Content-Disposition: form-data; name=\"field\"\r\n\
+            \r\n\
+            value\r\n\
+            --XYZ  \r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+            Content-Type: text/plain\r\n\
+            \r\n\
+            line one\r\n\r\nline --XYZ two\r\n\
+            --XYZ\r\n\
+            \r\n\
+            \r\n\
+            --XYZ--\r\n\
+            epilogue";
+        let parts = parse_multipart(body, b"XYZ");
+        assert_eq!(parts.len(), 3);
+        assert_eq!(
+            parts[0].headers.get("content-disposition"),
+            Some(&b"form-data; name=\"field\""[..])
+        );
+        assert_eq!(parts[0].body, b"value");
+        assert_eq!(
+            parts[1].headers.get("Content-Type"),
+            Some(&b"text/plain"[..])
+        );
+        assert_eq!(parts[1].body, b"line one\r\n\r\nline --XYZ two");
+        assert!(parts[2].headers.is_empty());
+        assert_eq!(parts[2].body, b"");
+    }
+
+    #[test]
+    fn test_parse_multipart_lf_and_unclosed() {
+        let body = b"--b\nA: 1\n\nfirst\n--b\nB: 2\n\nunclosed";
+        let parts = parse_multipart(body, b"b");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].headers.get("a"), Some(&b"1"[..]));
+        assert_eq!(parts[0].body, b"first");
+
+        assert!(parse_multipart(b"no boundary here", b"b").is_empty());
+        assert!(parse_multipart(b"--b--\r\n", b"b").is_empty());
+    }
+}
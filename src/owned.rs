@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::{HeaderSink, Request, Response};
+
+/// A request that owns its bytes instead of borrowing the input buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedRequest {
+    pub method: Vec<u8>,
+    pub url: Vec<u8>,
+    pub http_version: Vec<u8>,
+    pub headers: HashMap<Vec<u8>, Vec<u8>>,
+    pub body: Vec<u8>,
+}
+
+/// A response that owns its bytes instead of borrowing the input buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedResponse {
+    pub status: Vec<u8>,
+    pub status_code: Vec<u8>,
+    pub http_version: Vec<u8>,
+    pub headers: HashMap<Vec<u8>, Vec<u8>>,
+    pub body: Vec<u8>,
+}
+
+fn owned_headers<'k, 'v>(
+    headers: impl Iterator<Item = (&'k [u8], &'v [u8])>,
+) -> HashMap<Vec<u8>, Vec<u8>> {
+    headers
+        .map(|(key, value)| (key.to_vec(), value.to_vec()))
+        .collect()
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Copies the request so it no longer borrows the input buffer.
+    pub fn to_owned(&self) -> OwnedRequest {
+        OwnedRequest::from(self)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Copies the response so it no longer borrows the input buffer.
+    pub fn to_owned(&self) -> OwnedResponse {
+        OwnedResponse::from(self)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> From<&Request<'a, H>> for OwnedRequest {
+    fn from(request: &Request<'a, H>) -> OwnedRequest {
+        OwnedRequest {
+            method: request.method.to_vec(),
+            url: request.url.to_vec(),
+            http_version: request.http_version.to_vec(),
+            headers: owned_headers(request.headers_ordered()),
+            body: request.body.to_vec(),
+        }
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> From<&Response<'a, H>> for OwnedResponse {
+    fn from(response: &Response<'a, H>) -> OwnedResponse {
+        OwnedResponse {
+            status: response.status.to_vec(),
+            status_code: response.status_code.to_vec(),
+            http_version: response.http_version.to_vec(),
+            headers: owned_headers(response.headers_ordered()),
+            body: response.body.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_request, parse_response, OwnedRequest, OwnedResponse};
+
+    #[test]
+    fn test_to_owned() {
+        let request = {
+            let input = b"GET /index HTTP/1.1\r\nHost:x\r\n\r\n".to_vec();
+            parse_request(&input).unwrap().to_owned()
+        };
+        assert_eq!(request.method, b"GET");
+        assert_eq!(request.url, b"/index");
+        assert_eq!(request.headers.get(&b"Host"[..]), Some(&b"x".to_vec()));
+
+        let input = b"HTTP/1.1 200 OK\r\nContent-Length:2\r\n\r\nhi";
+        let response: OwnedResponse = parse_response(input).unwrap().to_owned();
+        assert_eq!(response.status_code, b"200");
+        assert_eq!(response.status, b"OK");
+        assert_eq!(response.body, b"hi");
+
+        let request = parse_request(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(OwnedRequest::from(&request), request.to_owned());
+    }
+}
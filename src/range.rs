@@ -0,0 +1,205 @@
+use crate::{trim_whitespace, HeaderSink, Request, Response};
+
+/// One range of a `Range: bytes=...` header. A suffix range such as `-500`
+/// has no `start` and an `end` holding the suffix length; an open-ended
+/// range such as `500-` has no `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+}
+
+/// The `Content-Range: bytes ...` of a partial response. `total` is `None`
+/// when the complete length is unknown (`*`). An unsatisfied range such as
+/// `bytes */2048` has neither `start` nor `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+    pub total: Option<u64>,
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Parses a `Range` header in `bytes` units. Returns `None` when the
+    /// header is absent, uses another unit or is malformed.
+    pub fn range(&self) -> Option<Vec<ByteRange>> {
+        let value = trim_whitespace(self.header("range")?);
+        let eq = value.iter().position(|b| *b == b'=')?;
+        if !trim_whitespace(&value[..eq]).eq_ignore_ascii_case(b"bytes") {
+            return None;
+        }
+        let ranges = value[eq + 1..]
+            .split(|b| *b == b',')
+            .map(trim_whitespace)
+            .filter(|spec| !spec.is_empty())
+            .map(parse_range)
+            .collect::<Option<Vec<_>>>()?;
+        if ranges.is_empty() {
+            return None;
+        }
+        Some(ranges)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Parses a `Content-Range` header in `bytes` units. Returns `None` when
+    /// the header is absent, uses another unit or is malformed.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        let value = trim_whitespace(self.header("content-range")?);
+        let space = value.iter().position(|b| *b == b' ')?;
+        if !value[..space].eq_ignore_ascii_case(b"bytes") {
+            return None;
+        }
+        let spec = trim_whitespace(&value[space + 1..]);
+        let slash = spec.iter().position(|b| *b == b'/')?;
+        let total = match &spec[slash + 1..] {
+            b"*" => None,
+            total => Some(parse_number(total)??),
+        };
+        let (start, end) = match &spec[..slash] {
+            b"*" => (None, None),
+            range => {
+                let dash = range.iter().position(|b| *b == b'-')?;
+                let start = parse_number(&range[..dash])??;
+                let end = parse_number(&range[dash + 1..])??;
+                if start > end || total.is_some_and(|total| end >= total) {
+                    return None;
+                }
+                (Some(start), Some(end))
+            }
+        };
+        if start.is_none() && total.is_none() {
+            return None;
+        }
+        Some(ContentRange { start, end, total })
+    }
+}
+
+fn parse_range(spec: &[u8]) -> Option<ByteRange> {
+    let dash = spec.iter().position(|b| *b == b'-')?;
+    let start = parse_number(&spec[..dash]);
+    let end = parse_number(&spec[dash + 1..]);
+    match (start, end) {
+        (Some(Some(start)), Some(Some(end))) if start > end => None,
+        (Some(start), Some(end)) if start.is_some() || end.is_some() => {
+            Some(ByteRange { start, end })
+        }
+        _ => None,
+    }
+}
+
+/// Parses an optional decimal number: `Some(None)` for an empty string and
+/// `None` for anything that is not all digits.
+fn parse_number(digits: &[u8]) -> Option<Option<u64>> {
+    if digits.is_empty() {
+        return Some(None);
+    }
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    core::str::from_utf8(digits).ok()?.parse().ok().map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request, parse_response};
+
+    fn range(value: &str) -> Option<Vec<ByteRange>> {
+        let input = format!("GET / HTTP/1.1\r\nRange: {}\r\n\r\n", value);
+        parse_request(input.as_bytes()).unwrap().range()
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(
+            range("bytes=0-499, 1000-"),
+            Some(vec![
+                ByteRange {
+                    start: Some(0),
+                    end: Some(499)
+                },
+                ByteRange {
+                    start: Some(1000),
+                    end: None
+                },
+            ])
+        );
+        assert_eq!(
+            range("Bytes = -500"),
+            Some(vec![ByteRange {
+                start: None,
+                end: Some(500)
+            }])
+        );
+        assert_eq!(range("bytes=5-5,,7-9").map(|r| r.len()), Some(2));
+    }
+
+    fn content_range(value: &str) -> Option<ContentRange> {
+        let input = format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: {}\r\n\r\n",
+            value
+        );
+        parse_response(input.as_bytes()).unwrap().content_range()
+    }
+
+    #[test]
+    fn test_content_range() {
+        assert_eq!(
+            content_range("bytes 200-1023/2048"),
+            Some(ContentRange {
+                start: Some(200),
+                end: Some(1023),
+                total: Some(2048)
+            })
+        );
+        assert_eq!(
+            content_range("bytes 0-99/*"),
+            Some(ContentRange {
+                start: Some(0),
+                end: Some(99),
+                total: None
+            })
+        );
+        assert_eq!(
+            content_range("bytes */2048"),
+            Some(ContentRange {
+                start: None,
+                end: None,
+                total: Some(2048)
+            })
+        );
+        for value in [
+            "bytes */*",
+            "bytes 5-1/10",
+            "bytes 0-10/10",
+            "bytes 0-/10",
+            "bytes 0-9",
+            "items 0-9/10",
+            "bytes=0-9/10",
+            "bytes a-b/10",
+        ] {
+            assert_eq!(content_range(value), None, "{}", value);
+        }
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert_eq!(result.content_range(), None);
+    }
+
+    #[test]
+    fn test_range_invalid() {
+        for value in [
+            "items=0-5",
+            "bytes=",
+            "bytes=-",
+            "bytes=5",
+            "bytes=9-1",
+            "bytes=a-b",
+            "bytes=0-1, x",
+            "0-5",
+        ] {
+            assert_eq!(range(value), None, "{}", value);
+        }
+        let result = parse_request(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.range(), None);
+    }
+}
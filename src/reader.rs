@@ -0,0 +1,229 @@
+use std::io::{self, Read};
+
+use crate::{
+    chunked, parse_request_head, parse_request_with_config, BodyFraming, OwnedRequest, ParseError,
+    ParserConfig,
+};
+
+/// Reads one request from `reader`.
+///
+/// The head is read a byte at a time so nothing past the message is
+/// consumed; wrap unbuffered sources such as sockets in a `BufReader`. The
+/// body is then read according to `Transfer-Encoding: chunked` or
+/// `Content-Length`, and is empty when neither is present. A body longer
+/// than `ParserConfig::max_body_bytes` fails with
+/// `io::ErrorKind::InvalidData`.
+pub fn parse_request_from_reader<R: Read>(reader: &mut R) -> io::Result<OwnedRequest> {
+    parse_request_from_reader_with_config(reader, &ParserConfig::default())
+}
+
+/// Like `parse_request_from_reader`, but with custom limits.
+pub fn parse_request_from_reader_with_config<R: Read>(
+    reader: &mut R,
+    config: &ParserConfig,
+) -> io::Result<OwnedRequest> {
+    let max_head = config.max_head();
+    let mut buf = vec![];
+    let mut byte = [0];
+    let (body_start, body_len) = loop {
+        // Retries reads that were interrupted, and fails with
+        // `UnexpectedEof` if the stream ends first.
+        reader.read_exact(&mut byte)?;
+        buf.push(byte[0]);
+        if buf.len() > max_head {
+            return Err(invalid_data(ParseError::HeaderTooLong));
+        }
+        if !buf.ends_with(b"\n\n") && !buf.ends_with(b"\n\r\n") {
+            continue;
+        }
+        let mut framing = BodyFraming::default();
+        let head = parse_request_head(&buf, config, &mut |key, value| {
+            framing.add(key, value);
+            Ok(())
+        });
+        match head {
+            Ok(head) if framing.is_chunked() => break (head.body, None),
+            Ok(head) => {
+                let len = framing.content_length().map_err(invalid_data)?;
+                break (head.body, Some(len.unwrap_or(0)));
+            }
+            Err(ParseError::TruncatedInput | ParseError::Empty) => continue,
+            Err(err) => return Err(invalid_data(err)),
+        }
+    };
+
+    match body_len {
+        Some(len) => {
+            if len > config.max_body_bytes {
+                return Err(invalid_data(ParseError::BodyTooLong));
+            }
+            buf.resize(body_start + len, 0);
+            reader.read_exact(&mut buf[body_start..])?;
+        }
+        None => read_chunked(
+            reader,
+            &mut buf,
+            body_start.saturating_add(config.max_body_bytes),
+        )?,
+    }
+
+    let request = parse_request_with_config(&buf, config).map_err(invalid_data)?;
+    Ok(OwnedRequest::from(&request))
+}
+
+/// Reads a chunked body onto the end of `buf`: each chunk, the zero-length
+/// chunk and the trailer section, without reading past its blank line.
+fn read_chunked<R: Read>(reader: &mut R, buf: &mut Vec<u8>, limit: usize) -> io::Result<()> {
+    loop {
+        let line = read_line(reader, buf, limit)?;
+        let size = chunked::chunk_size(&buf[line..buf.len() - 1]).ok_or_else(|| {
+            invalid_data(ParseError::UnexpectedByte {
+                offset: line,
+                byte: buf[line],
+            })
+        })?;
+        if size == 0 {
+            break;
+        }
+        let start = buf.len();
+        match start.checked_add(size) {
+            Some(end) if end <= limit => buf.resize(end, 0),
+            _ => return Err(invalid_data(ParseError::BodyTooLong)),
+        }
+        reader.read_exact(&mut buf[start..])?;
+        read_line(reader, buf, limit)?;
+    }
+    // Trailer fields, up to the blank line.
+    loop {
+        let line = read_line(reader, buf, limit)?;
+        if matches!(&buf[line..], b"\n" | b"\r\n") {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads a byte at a time through the next LF, appending to `buf`, and
+/// returns where the line starts.
+fn read_line<R: Read>(reader: &mut R, buf: &mut Vec<u8>, limit: usize) -> io::Result<usize> {
+    let start = buf.len();
+    let mut byte = [0];
+    while !buf.ends_with(b"\n") || buf.len() == start {
+        if buf.len() >= limit {
+            return Err(invalid_data(ParseError::BodyTooLong));
+        }
+        reader.read_exact(&mut byte)?;
+        buf.push(byte[0]);
+    }
+    Ok(start)
+}
+
+fn invalid_data(err: ParseError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_from_reader() {
+        let mut input =
+            &b"POST /a HTTP/1.1\r\nContent-Length:3\r\n\r\nabcGET /b HTTP/1.1\r\n\r\n"[..];
+        let request = parse_request_from_reader(&mut input).unwrap();
+        assert_eq!(request.method, b"POST");
+        assert_eq!(request.url, b"/a");
+        assert_eq!(
+            request.headers.get(&b"Content-Length"[..]),
+            Some(&b"3".to_vec())
+        );
+        assert_eq!(request.body, b"abc");
+
+        let request = parse_request_from_reader(&mut input).unwrap();
+        assert_eq!(request.url, b"/b");
+        assert_eq!(request.body, b"");
+
+        let err = parse_request_from_reader(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_parse_request_from_reader_chunked() {
+        let mut input = &b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            3;x=y\r\nabc\r\n2\r\nde\r\n0\r\nX-T: 1\r\n\r\nGET /b HTTP/1.1\r\n\r\n"[..];
+        let request = parse_request_from_reader(&mut input).unwrap();
+        assert_eq!(
+            request.body,
+            b"3;x=y\r\nabc\r\n2\r\nde\r\n0\r\nX-T: 1\r\n\r\n"
+        );
+        let request = parse_request_from_reader(&mut input).unwrap();
+        assert_eq!(request.url, b"/b");
+
+        let mut input = &b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n"[..];
+        let err = parse_request_from_reader(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut input = &b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nab"[..];
+        let err = parse_request_from_reader(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_parse_request_from_reader_body_limit() {
+        let mut input = &b"POST /a HTTP/1.1\r\nContent-Length: 18446744073709551000\r\n\r\n"[..];
+        let err = parse_request_from_reader(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let config = ParserConfig {
+            max_body_bytes: 4,
+            ..ParserConfig::default()
+        };
+        let mut input = &b"POST /a HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello"[..];
+        let err = parse_request_from_reader_with_config(&mut input, &config).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut input = &b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            ffffffffffffffff\r\nabc"[..];
+        let err = parse_request_from_reader_with_config(&mut input, &config).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut input = &b"POST /a HTTP/1.1\r\nContent-Length: 4\r\n\r\nhell"[..];
+        let request = parse_request_from_reader_with_config(&mut input, &config).unwrap();
+        assert_eq!(request.body, b"hell");
+    }
+
+    #[test]
+    fn test_parse_request_from_reader_short_body() {
+        let mut input = &b"POST /a HTTP/1.1\r\nContent-Length:10\r\n\r\nabc"[..];
+        let err = parse_request_from_reader(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    /// Fails every other read with `io::ErrorKind::Interrupted`, as a read
+    /// cut short by a signal does.
+    struct Interrupting<R> {
+        inner: R,
+        interrupt: bool,
+    }
+
+    impl<R: Read> Read for Interrupting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_parse_request_from_reader_interrupted() {
+        let mut input = Interrupting {
+            inner: &b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n"
+                [..],
+            interrupt: false,
+        };
+        let request = parse_request_from_reader(&mut input).unwrap();
+        assert_eq!(request.url, b"/a");
+        assert_eq!(request.body, b"3\r\nabc\r\n0\r\n\r\n");
+
+        let err = parse_request_from_reader(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
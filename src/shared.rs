@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+
+use crate::{parse_request, parse_response, ParseError};
+
+/// A request whose fields are slices of one shared `Bytes` buffer, so it
+/// owns its data without copying it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytesRequest {
+    pub method: Bytes,
+    pub url: Bytes,
+    pub http_version: Bytes,
+    pub headers: HashMap<Bytes, Bytes>,
+    pub body: Bytes,
+}
+
+/// A response whose fields are slices of one shared `Bytes` buffer, so it
+/// owns its data without copying it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytesResponse {
+    pub status: Bytes,
+    pub status_code: Bytes,
+    pub http_version: Bytes,
+    pub headers: HashMap<Bytes, Bytes>,
+    pub body: Bytes,
+}
+
+fn shared_headers<'k, 'v>(
+    buf: &Bytes,
+    headers: impl Iterator<Item = (&'k [u8], &'v [u8])>,
+) -> HashMap<Bytes, Bytes> {
+    headers
+        .map(|(key, value)| (share(buf, key), share(buf, value)))
+        .collect()
+}
+
+/// Slices `buf` where `bytes` borrows from it, and copies anything else,
+/// such as a header value that was unfolded.
+fn share(buf: &Bytes, bytes: &[u8]) -> Bytes {
+    if bytes.is_empty() || buf.as_ptr_range().contains(&bytes.as_ptr()) {
+        buf.slice_ref(bytes)
+    } else {
+        Bytes::copy_from_slice(bytes)
+    }
+}
+
+/// Parses a request held in `buf`. The fields point into `buf` and keep
+/// it alive; a repeated header keeps its last value.
+pub fn parse_request_bytes(buf: Bytes) -> Result<BytesRequest, ParseError> {
+    let request = parse_request(&buf)?;
+    Ok(BytesRequest {
+        method: buf.slice_ref(request.method),
+        url: buf.slice_ref(request.url),
+        http_version: buf.slice_ref(request.http_version),
+        headers: shared_headers(&buf, request.headers_ordered()),
+        body: buf.slice_ref(request.body),
+    })
+}
+
+/// Parses a response held in `buf`. The fields point into `buf` and keep
+/// it alive; a repeated header keeps its last value.
+pub fn parse_response_bytes(buf: Bytes) -> Result<BytesResponse, ParseError> {
+    let response = parse_response(&buf)?;
+    Ok(BytesResponse {
+        status: buf.slice_ref(response.status),
+        status_code: buf.slice_ref(response.status_code),
+        http_version: buf.slice_ref(response.http_version),
+        headers: shared_headers(&buf, response.headers_ordered()),
+        body: buf.slice_ref(response.body),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_bytes() {
+        let buf = Bytes::from_static(b"POST /a HTTP/1.1\r\nHost: x\r\nContent-Length: 2\r\n\r\nhi");
+        let request = parse_request_bytes(buf.clone()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "/a");
+        assert_eq!(request.headers[&b"Host"[..]], "x");
+        assert_eq!(request.body, "hi");
+        assert_eq!(request.body.as_ptr(), buf[buf.len() - 2..].as_ptr());
+
+        let buf = Bytes::from_static(b"GET / HTTP/1.1\r\nX-A: 1\r\n 2\r\n\r\n");
+        let request = parse_request_bytes(buf).unwrap();
+        assert_eq!(request.headers[&b"X-A"[..]], "1 2");
+    }
+
+    #[test]
+    fn test_parse_response_bytes() {
+        let buf = Bytes::from(b"HTTP/1.1 204 No Content\r\nServer: x\r\n\r\n".to_vec());
+        let response = parse_response_bytes(buf).unwrap();
+        assert_eq!(response.status_code, "204");
+        assert_eq!(response.status, "No Content");
+        assert_eq!(response.headers[&b"Server"[..]], "x");
+        assert!(response.body.is_empty());
+
+        let buf = Bytes::from_static(b"HTTP/1.1\r\n\r\n");
+        assert_eq!(
+            parse_response_bytes(buf).err(),
+            Some(ParseError::MissingStatusLine)
+        );
+    }
+}
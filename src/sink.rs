@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use crate::Headers;
+
+/// A collection that parsed headers are inserted into, in the order they
+/// appear in the message. Values are the spans of the input, so one folded
+/// over several lines (obs-fold) keeps its line breaks; `Headers` is the
+/// sink that unfolds them.
+pub trait HeaderSink<'a> {
+    fn insert(&mut self, key: &'a [u8], value: &'a [u8]);
+
+    /// Inserts a header whose name should be stored lowercased, as
+    /// `ParserConfig::lowercase_headers` asks. Sinks that can only hold
+    /// borrowed names store it as sent.
+    fn insert_lowercase(&mut self, key: &'a [u8], value: &'a [u8]) {
+        self.insert(key, value);
+    }
+
+    /// Returns the sink itself if it keeps every header in order, so the
+    /// message can answer lookups from it without a second copy. Only
+    /// `Headers` does; messages collected into other sinks parse a
+    /// `Headers` of their own only once a lookup needs it.
+    fn ordered(&self) -> Option<&Headers<'a>> {
+        None
+    }
+}
+
+/// Keys are case-sensitive and a repeated header keeps its last value.
+impl<'a, S: BuildHasher> HeaderSink<'a> for HashMap<&'a [u8], &'a [u8], S> {
+    fn insert(&mut self, key: &'a [u8], value: &'a [u8]) {
+        HashMap::insert(self, key, value);
+    }
+}
+
+/// Keys are case-sensitive and a repeated header keeps its last value.
+impl<'a> HeaderSink<'a> for BTreeMap<&'a [u8], &'a [u8]> {
+    fn insert(&mut self, key: &'a [u8], value: &'a [u8]) {
+        BTreeMap::insert(self, key, value);
+    }
+}
+
+/// Keeps every header, repeated ones included, in wire order.
+impl<'a> HeaderSink<'a> for Vec<(&'a [u8], &'a [u8])> {
+    fn insert(&mut self, key: &'a [u8], value: &'a [u8]) {
+        self.push((key, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request_with_headers, parse_response_with_headers, Request};
+
+    /// Collects every value of each header name.
+    #[derive(Default)]
+    struct MultiMap<'a>(BTreeMap<&'a [u8], Vec<&'a [u8]>>);
+
+    impl<'a> HeaderSink<'a> for MultiMap<'a> {
+        fn insert(&mut self, key: &'a [u8], value: &'a [u8]) {
+            self.0.entry(key).or_default().push(value);
+        }
+    }
+
+    const INPUT: &[u8] = b"GET / HTTP/1.1\r\nb: 1\r\na: 2\r\nb: 3\r\n\r\n";
+
+    #[test]
+    fn test_btree_map() {
+        let result: Request<BTreeMap<_, _>> = parse_request_with_headers(INPUT).unwrap();
+        let keys: Vec<_> = result.headers.keys().copied().collect();
+        assert_eq!(keys, [&b"a"[..], b"b"]);
+        assert_eq!(result.headers[&b"b"[..]], b"3");
+        assert_eq!(result.header_all("b"), [&b"1"[..], b"3"]);
+    }
+
+    #[test]
+    fn test_vec() {
+        let result =
+            parse_response_with_headers::<Vec<_>>(b"HTTP/1.1 200 OK\r\nb: 1\r\nb: 2\r\n\r\n")
+                .unwrap();
+        assert_eq!(result.headers, [(&b"b"[..], &b"1"[..]), (b"b", b"2")]);
+        assert_eq!(result.status_code_u16(), Some(200));
+    }
+
+    #[test]
+    fn test_custom_sink() {
+        let result = parse_request_with_headers::<MultiMap>(INPUT).unwrap();
+        assert_eq!(result.headers.0[&b"b"[..]], [&b"1"[..], b"3"]);
+        assert_eq!(result.headers.0[&b"a"[..]], [&b"2"[..]]);
+    }
+
+    #[test]
+    fn test_lookups_on_unordered_sink() {
+        let input = b"GET / HTTP/1.1\r\nX-A: 1\r\n\t2\r\nb: 3\r\n\r\n";
+        let result: Request<HashMap<_, _>> = parse_request_with_headers(input).unwrap();
+        assert_eq!(result.headers[&b"X-A"[..]], b"1\r\n\t2");
+        assert_eq!(result.header("x-a"), Some(&b"1 2"[..]));
+        let ordered: Vec<_> = result.headers_ordered().collect();
+        assert_eq!(ordered, [(&b"X-A"[..], &b"1 2"[..]), (b"b", b"3")]);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    find_header, parse_request_head, request_body_slice, BodyFraming, ParseError, ParserConfig,
+};
+
+/// A request whose headers are a plain vector of borrowed name and value
+/// pairs in input order, for callers that want to own the list rather than
+/// go through `Headers`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSlices<'a> {
+    pub method: &'a [u8],
+    pub url: &'a [u8],
+    pub http_version: &'a [u8],
+    pub headers: Vec<(&'a [u8], &'a [u8])>,
+    pub body: &'a [u8],
+}
+
+impl<'a> RequestSlices<'a> {
+    /// Looks up a header value, ignoring ASCII case in the name.
+    pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+        find_header(self.headers.iter().copied(), name.as_bytes())
+    }
+}
+
+/// Parses a request like `parse_request`, but returns the headers as a
+/// plain vector.
+pub fn parse_request_slices(data: &[u8]) -> Result<RequestSlices<'_>, ParseError> {
+    let mut headers = vec![];
+    let head = parse_request_head(data, &ParserConfig::default(), &mut |key, value| {
+        headers.push((key, value));
+        Ok(())
+    })?;
+    let body = request_body_slice(data, head.body, &BodyFraming::new(headers.iter().copied()))?;
+    let [method, url, http_version] = head.line;
+    Ok(RequestSlices {
+        method,
+        url,
+        http_version,
+        headers,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_slices() {
+        let input =
+            b"POST /a HTTP/1.1\r\nHost: x\r\nAccept: */*\r\nhost: y\r\nContent-Length: 2\r\n\r\nhi";
+        let result = parse_request_slices(input).unwrap();
+        assert_eq!(result.method, b"POST");
+        assert_eq!(result.url, b"/a");
+        assert_eq!(
+            result.headers,
+            [
+                (&b"Host"[..], &b"x"[..]),
+                (b"Accept", b"*/*"),
+                (b"host", b"y"),
+                (b"Content-Length", b"2"),
+            ]
+        );
+        assert_eq!(result.header("HOST"), Some(&b"x"[..]));
+        assert_eq!(result.body, b"hi");
+    }
+}
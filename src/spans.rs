@@ -0,0 +1,66 @@
+use core::ops::Range;
+
+use crate::{parse_request_head, request_body_slice, BodyFraming, ParseError, ParserConfig};
+
+/// The positions of each part of a request within the parsed buffer, for
+/// splicing replacements into the original bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSpans {
+    pub method: Range<usize>,
+    pub url: Range<usize>,
+    pub http_version: Range<usize>,
+    /// Key and value ranges of each header, in the order they appeared.
+    pub headers: Vec<(Range<usize>, Range<usize>)>,
+    pub body: Range<usize>,
+}
+
+/// Parses a request like `parse_request`, returning where each field lives
+/// in `data` instead of the fields themselves.
+pub fn parse_request_spans(data: &[u8]) -> Result<RequestSpans, ParseError> {
+    let mut fields = vec![];
+    let head = parse_request_head(data, &ParserConfig::default(), &mut |key, value| {
+        fields.push((key, value));
+        Ok(())
+    })?;
+    let body = request_body_slice(data, head.body, &BodyFraming::new(fields.iter().copied()))?;
+    let [method, url, http_version] = head.line;
+    Ok(RequestSpans {
+        method: range(data, method),
+        url: range(data, url),
+        http_version: range(data, http_version),
+        headers: fields
+            .iter()
+            .map(|(key, value)| (range(data, key), range(data, value)))
+            .collect(),
+        body: range(data, body),
+    })
+}
+
+/// The position of `slice`, which must borrow from `data`.
+fn range(data: &[u8], slice: &[u8]) -> Range<usize> {
+    let start = slice.as_ptr() as usize - data.as_ptr() as usize;
+    debug_assert!(start + slice.len() <= data.len());
+    start..start + slice.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_spans() {
+        let input = b"POST /submit HTTP/1.1\r\nHost: example.com\r\nContent-Length: 4\r\n\r\nbody";
+        let spans = parse_request_spans(input).unwrap();
+        assert_eq!(&input[spans.method.clone()], b"POST");
+        assert_eq!(&input[spans.url.clone()], b"/submit");
+        assert_eq!(&input[spans.http_version.clone()], b"HTTP/1.1");
+        assert_eq!(spans.url, 5..12);
+        let (key, value) = spans.headers[0].clone();
+        assert_eq!(&input[key], b"Host");
+        assert_eq!(&input[value], b"example.com");
+        let (key, value) = spans.headers[1].clone();
+        assert_eq!(&input[key], b"Content-Length");
+        assert_eq!(&input[value], b"4");
+        assert_eq!(&input[spans.body], b"body");
+    }
+}
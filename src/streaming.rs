@@ -0,0 +1,251 @@
+use crate::{
+    chunked, parse_request_head, parse_request_with_config, BodyFraming, OwnedRequest, ParseError,
+    ParserConfig,
+};
+
+/// The outcome of `StreamingParser::poll`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseProgress {
+    /// A full request was buffered; its bytes have been removed from the parser.
+    Complete(OwnedRequest),
+    /// More data is needed before a request can be returned.
+    NeedMore,
+    /// The buffered data is not a valid request.
+    Error(ParseError),
+}
+
+/// Parses requests from data that arrives in pieces, such as reads from a
+/// non-blocking socket.
+///
+/// Bytes passed to `push` are buffered, and each `poll` only scans what
+/// arrived since the last call while looking for the end of the head. A
+/// chunked body is rescanned on each poll until its last chunk and trailers
+/// have arrived. Once a request is complete it is removed from the buffer,
+/// so pipelined requests are returned by subsequent polls.
+#[derive(Debug, Default)]
+pub struct StreamingParser {
+    config: ParserConfig,
+    buf: Vec<u8>,
+    // How far the buffer has been scanned for the end of the head.
+    scanned: usize,
+    // Non-CR bytes seen on the line being scanned.
+    line_len: usize,
+    // Whether a non-empty line has been seen, so a blank line ends the head.
+    seen_line: bool,
+    // How the body is framed once the head has been parsed.
+    framing: Option<Framing>,
+}
+
+/// Where a buffered request ends.
+#[derive(Debug, Clone, Copy)]
+enum Framing {
+    /// The total length of the request, known from `Content-Length`.
+    Length(usize),
+    /// A chunked body starting at this offset, which ends once its last
+    /// chunk and trailers have arrived.
+    Chunked(usize),
+}
+
+impl StreamingParser {
+    pub fn new() -> StreamingParser {
+        StreamingParser::default()
+    }
+
+    pub fn with_config(config: ParserConfig) -> StreamingParser {
+        StreamingParser {
+            config,
+            ..StreamingParser::default()
+        }
+    }
+
+    /// Appends received bytes to the internal buffer.
+    pub fn push(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    pub fn poll(&mut self) -> ParseProgress {
+        let framing = match self.framing {
+            Some(framing) => framing,
+            None => {
+                let head_len = match self.scan_head() {
+                    Some(len) => len,
+                    None if self.buf.len() > self.config.max_head() => {
+                        return ParseProgress::Error(ParseError::HeaderTooLong)
+                    }
+                    None => return ParseProgress::NeedMore,
+                };
+                match self.measure(head_len) {
+                    Ok(len) => len,
+                    Err(err) => return ParseProgress::Error(err),
+                }
+            }
+        };
+        self.framing = Some(framing);
+        let message_len = match framing {
+            Framing::Length(len) if self.buf.len() >= len => len,
+            Framing::Chunked(body) => match chunked::body_len(&self.buf[body..]) {
+                Some(len) if len <= self.config.max_body_bytes => body + len,
+                None if self.buf.len() - body <= self.config.max_body_bytes => {
+                    return ParseProgress::NeedMore
+                }
+                _ => return ParseProgress::Error(ParseError::BodyTooLong),
+            },
+            Framing::Length(_) => return ParseProgress::NeedMore,
+        };
+
+        let progress = match parse_request_with_config(&self.buf[..message_len], &self.config) {
+            Ok(request) => ParseProgress::Complete(request.to_owned()),
+            Err(err) => return ParseProgress::Error(err),
+        };
+        self.buf.drain(..message_len);
+        self.scanned = 0;
+        self.line_len = 0;
+        self.seen_line = false;
+        self.framing = None;
+        progress
+    }
+
+    /// Scans newly buffered bytes for the blank line ending the head and
+    /// returns the head length once it is found.
+    fn scan_head(&mut self) -> Option<usize> {
+        while self.scanned < self.buf.len() {
+            let current = self.buf[self.scanned];
+            self.scanned += 1;
+            match current {
+                b'\n' if self.line_len == 0 && self.seen_line => return Some(self.scanned),
+                b'\n' => {
+                    self.seen_line |= self.line_len > 0;
+                    self.line_len = 0;
+                }
+                b'\r' => {}
+                _ => self.line_len += 1,
+            }
+        }
+        None
+    }
+
+    /// Parses the head and works out how the request's end is found.
+    fn measure(&self, head_len: usize) -> Result<Framing, ParseError> {
+        let mut framing = BodyFraming::default();
+        let head = parse_request_head(&self.buf[..head_len], &self.config, &mut |key, value| {
+            framing.add(key, value);
+            Ok(())
+        })?;
+        if framing.is_chunked() {
+            return Ok(Framing::Chunked(head.body));
+        }
+        let body_len = framing.content_length()?.unwrap_or(0);
+        if body_len > self.config.max_body_bytes {
+            return Err(ParseError::BodyTooLong);
+        }
+        head.body
+            .checked_add(body_len)
+            .map(Framing::Length)
+            .ok_or(ParseError::InvalidContentLength)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_incremental() {
+        let input = b"POST /a HTTP/1.1\r\nContent-Length:3\r\n\r\nabc";
+        let mut parser = StreamingParser::new();
+        for b in &input[..input.len() - 1] {
+            parser.push(&[*b]);
+            assert_eq!(parser.poll(), ParseProgress::NeedMore);
+        }
+        parser.push(&input[input.len() - 1..]);
+        match parser.poll() {
+            ParseProgress::Complete(request) => {
+                assert_eq!(request.url, b"/a");
+                assert_eq!(request.body, b"abc");
+            }
+            progress => panic!("unexpected {:?}", progress),
+        }
+        assert_eq!(parser.poll(), ParseProgress::NeedMore);
+    }
+
+    #[test]
+    fn test_poll_pipelined() {
+        let mut parser = StreamingParser::new();
+        parser.push(b"GET /a HTTP/1.1\r\n\r\nGET /b HTTP/1.1\r\n\r\nGET /c");
+        let mut urls = vec![];
+        while let ParseProgress::Complete(request) = parser.poll() {
+            urls.push(request.url);
+        }
+        assert_eq!(urls, vec![b"/a".to_vec(), b"/b".to_vec()]);
+        assert_eq!(parser.poll(), ParseProgress::NeedMore);
+    }
+
+    #[test]
+    fn test_poll_chunked() {
+        let input = b"POST /a HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            3\r\nabc\r\n0\r\n\r\nGET /b HTTP/1.1\r\n\r\n";
+        let mut parser = StreamingParser::new();
+        let end = input.len() - b"GET /b HTTP/1.1\r\n\r\n".len();
+        for b in &input[..end - 1] {
+            parser.push(&[*b]);
+            assert_eq!(parser.poll(), ParseProgress::NeedMore);
+        }
+        parser.push(&input[end - 1..]);
+        match parser.poll() {
+            ParseProgress::Complete(request) => {
+                assert_eq!(request.url, b"/a");
+                assert_eq!(request.body, b"3\r\nabc\r\n0\r\n\r\n");
+            }
+            progress => panic!("unexpected {:?}", progress),
+        }
+        match parser.poll() {
+            ParseProgress::Complete(request) => assert_eq!(request.url, b"/b"),
+            progress => panic!("unexpected {:?}", progress),
+        }
+    }
+
+    #[test]
+    fn test_poll_with_config() {
+        let config = ParserConfig {
+            tolerant: true,
+            allow_tab_separators: true,
+            ..ParserConfig::default()
+        };
+        let mut parser = StreamingParser::with_config(config);
+        parser.push(b"GET\t/a HTTP/1.1\r\nno colon\r\nHost: x\r\n\r\n");
+        match parser.poll() {
+            ParseProgress::Complete(request) => {
+                assert_eq!(request.url, b"/a");
+                assert_eq!(request.headers.len(), 1);
+            }
+            progress => panic!("unexpected {:?}", progress),
+        }
+    }
+
+    #[test]
+    fn test_poll_body_limit() {
+        let config = ParserConfig {
+            max_body_bytes: 4,
+            ..ParserConfig::default()
+        };
+        let mut parser = StreamingParser::with_config(config);
+        parser.push(b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\n");
+        assert_eq!(parser.poll(), ParseProgress::Error(ParseError::BodyTooLong));
+
+        let mut parser = StreamingParser::with_config(config);
+        parser.push(b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n");
+        assert_eq!(parser.poll(), ParseProgress::NeedMore);
+        parser.push(b"abc");
+        assert_eq!(parser.poll(), ParseProgress::Error(ParseError::BodyTooLong));
+    }
+
+    #[test]
+    fn test_poll_error() {
+        let mut parser = StreamingParser::new();
+        parser.push(b"GET / FOO\r\n\r\n");
+        assert_eq!(
+            parser.poll(),
+            ParseProgress::Error(ParseError::InvalidVersion)
+        );
+    }
+}
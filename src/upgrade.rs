@@ -0,0 +1,53 @@
+use crate::{has_token, HeaderSink, Request, Response};
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Whether this is a WebSocket opening handshake (RFC 6455 §4.1): it
+    /// asks to upgrade to `websocket`, lists `upgrade` in `Connection` and
+    /// carries a `Sec-WebSocket-Key`.
+    pub fn is_websocket_upgrade(&self) -> bool {
+        let lists = |name: &str, token: &[u8]| {
+            self.header_all(name)
+                .into_iter()
+                .any(|value| has_token(value, token))
+        };
+        lists("upgrade", b"websocket")
+            && lists("connection", b"upgrade")
+            && self.header("sec-websocket-key").is_some()
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Whether the server accepted a protocol upgrade with
+    /// `101 Switching Protocols`.
+    pub fn is_switching_protocols(&self) -> bool {
+        self.status_code == b"101"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_is_websocket_upgrade() {
+        let input = b"GET /chat HTTP/1.1\r\nHost: x\r\nUpgrade: WebSocket\r\n\
+            Connection: keep-alive, Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZQ==\r\n\r\n";
+        assert!(parse_request(input).unwrap().is_websocket_upgrade());
+
+        let input = b"GET /chat HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\n";
+        assert!(!parse_request(input).unwrap().is_websocket_upgrade());
+        let input = b"GET /chat HTTP/1.1\r\nUpgrade: h2c\r\nConnection: Upgrade\r\n\
+            Sec-WebSocket-Key: x\r\n\r\n";
+        assert!(!parse_request(input).unwrap().is_websocket_upgrade());
+        let input = b"GET /chat HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: x\r\n\r\n";
+        assert!(!parse_request(input).unwrap().is_websocket_upgrade());
+    }
+
+    #[test]
+    fn test_is_switching_protocols() {
+        let input = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n";
+        assert!(parse_response(input).unwrap().is_switching_protocols());
+        let input = b"HTTP/1.1 200 OK\r\n\r\n";
+        assert!(!parse_response(input).unwrap().is_switching_protocols());
+    }
+}
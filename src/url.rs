@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use crate::{trim_whitespace, HeaderSink, Request};
+
+/// The form of a request target (RFC 7230 §5.3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestTarget<'a> {
+    /// A path with an optional query, as sent to an origin server.
+    Origin(&'a [u8]),
+    /// A full URI, as sent to a proxy. `authority` is empty when the URI has
+    /// none, and `path` keeps any query string.
+    Absolute {
+        scheme: &'a [u8],
+        authority: &'a [u8],
+        path: &'a [u8],
+    },
+    /// A `host:port` pair, used by `CONNECT`.
+    Authority(&'a [u8]),
+    /// The `*` of a server-wide `OPTIONS` request.
+    Asterisk,
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Returns the URL without its query string.
+    pub fn path(&self) -> &'a [u8] {
+        match self.url.iter().position(|b| *b == b'?') {
+            Some(i) => &self.url[..i],
+            None => self.url,
+        }
+    }
+
+    /// Returns the part of the URL after the `?`, if there is one.
+    pub fn query(&self) -> Option<&'a [u8]> {
+        self.url
+            .iter()
+            .position(|b| *b == b'?')
+            .map(|i| &self.url[i + 1..])
+    }
+
+    /// Splits the query string into `key=value` pairs. A key without `=`
+    /// maps to an empty value and later duplicates overwrite earlier ones.
+    pub fn query_params(&self) -> HashMap<&'a [u8], &'a [u8]> {
+        let query = match self.query() {
+            Some(query) => query,
+            None => return HashMap::new(),
+        };
+        query
+            .split(|b| *b == b'&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.iter().position(|b| *b == b'=') {
+                Some(i) => (&pair[..i], &pair[i + 1..]),
+                None => (pair, &pair[pair.len()..]),
+            })
+            .collect()
+    }
+
+    /// Decodes an `application/x-www-form-urlencoded` body into `key=value`
+    /// pairs, turning `+` into a space and decoding `%XX` escapes. A key
+    /// without `=` maps to an empty value and later duplicates overwrite
+    /// earlier ones. Returns `None` for other content types.
+    pub fn form_params(&self) -> Option<HashMap<Vec<u8>, Vec<u8>>> {
+        let content_type = self.content_type()?;
+        if !content_type
+            .mime
+            .eq_ignore_ascii_case(b"application/x-www-form-urlencoded")
+        {
+            return None;
+        }
+        let decode = |part: &[u8]| {
+            let spaced: Vec<u8> = part
+                .iter()
+                .map(|b| if *b == b'+' { b' ' } else { *b })
+                .collect();
+            percent_decode(&spaced)
+        };
+        let params = self
+            .body
+            .split(|b| *b == b'&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.iter().position(|b| *b == b'=') {
+                Some(i) => (decode(&pair[..i]), decode(&pair[i + 1..])),
+                None => (decode(pair), vec![]),
+            })
+            .collect();
+        Some(params)
+    }
+
+    /// Collects the `;key=value` parameters of every path segment, in order,
+    /// e.g. `version=2` from `/file;version=2/sub`. A parameter without `=`
+    /// has an empty value.
+    pub fn path_params(&self) -> Vec<(&'a [u8], &'a [u8])> {
+        self.path()
+            .split(|b| *b == b'/')
+            .flat_map(|segment| segment.split(|b| *b == b';').skip(1))
+            .filter(|param| !param.is_empty())
+            .map(|param| match param.iter().position(|b| *b == b'=') {
+                Some(i) => (&param[..i], &param[i + 1..]),
+                None => (param, &param[param.len()..]),
+            })
+            .collect()
+    }
+
+    /// Returns the path with `%XX` escapes decoded. Invalid escapes are kept
+    /// as they are.
+    pub fn decoded_path(&self) -> Vec<u8> {
+        percent_decode(self.path())
+    }
+
+    /// Returns the decoded path with empty and `.` segments dropped and each
+    /// `..` removing the segment before it, never going above the root. A
+    /// trailing slash is kept, so `/a//b/../c/` becomes `/a/c/`.
+    pub fn normalized_path(&self) -> Vec<u8> {
+        let decoded = self.decoded_path();
+        let mut segments: Vec<&[u8]> = vec![];
+        let mut trailing_slash = false;
+        for segment in decoded.split(|b| *b == b'/') {
+            trailing_slash = matches!(segment, b"" | b"." | b"..");
+            match segment {
+                b"" | b"." => {}
+                b".." => {
+                    segments.pop();
+                }
+                segment => segments.push(segment),
+            }
+        }
+        let mut normalized = vec![b'/'];
+        normalized.extend_from_slice(&segments.join(&b'/'));
+        if trailing_slash && !segments.is_empty() {
+            normalized.push(b'/');
+        }
+        normalized
+    }
+
+    /// Classifies the URL by form. Targets that fit no form are returned
+    /// as `Origin`.
+    pub fn request_target(&self) -> RequestTarget<'a> {
+        let url = self.url;
+        if url == b"*" {
+            return RequestTarget::Asterisk;
+        }
+        if url.starts_with(b"/") {
+            return RequestTarget::Origin(url);
+        }
+        if self.method == b"CONNECT" {
+            return RequestTarget::Authority(url);
+        }
+        let is_scheme = |(i, b): (usize, &u8)| {
+            b.is_ascii_alphabetic() || (i > 0 && (b.is_ascii_digit() || b"+-.".contains(b)))
+        };
+        match url.iter().position(|b| *b == b':') {
+            Some(colon) if colon > 0 && url[..colon].iter().enumerate().all(is_scheme) => {
+                let scheme = &url[..colon];
+                let rest = &url[colon + 1..];
+                let (authority, path) = match rest.strip_prefix(b"//") {
+                    Some(rest) => {
+                        let end = rest
+                            .iter()
+                            .position(|b| matches!(b, b'/' | b'?' | b'#'))
+                            .unwrap_or(rest.len());
+                        rest.split_at(end)
+                    }
+                    None => (&rest[..0], rest),
+                };
+                RequestTarget::Absolute {
+                    scheme,
+                    authority,
+                    path,
+                }
+            }
+            _ => RequestTarget::Origin(url),
+        }
+    }
+
+    /// Splits the `Host` header into the host and its port, if one is
+    /// given. An IPv6 literal keeps its brackets, so `[::1]:8080` yields
+    /// `[::1]` and `8080`. Returns `None` when the header is missing or
+    /// empty, or the port is not a number. A colon is only allowed inside
+    /// an IP literal's brackets (RFC 3986 §3.2.2), so a host with more than
+    /// one outside them, such as `x:1:2` or an unbracketed `::1`, is
+    /// rejected rather than split.
+    pub fn host(&self) -> Option<(&[u8], Option<u16>)> {
+        let value = trim_whitespace(self.header("host")?);
+        let host_end = if value.starts_with(b"[") {
+            value.iter().position(|b| *b == b']')? + 1
+        } else {
+            value.iter().position(|b| *b == b':').unwrap_or(value.len())
+        };
+        let (host, port) = value.split_at(host_end);
+        if host.is_empty() {
+            return None;
+        }
+        let port = match port {
+            [] | [b':'] => None,
+            [b':', digits @ ..] if digits.iter().all(u8::is_ascii_digit) => {
+                Some(core::str::from_utf8(digits).ok()?.parse().ok()?)
+            }
+            _ => return None,
+        };
+        Some((host, port))
+    }
+
+    /// Splits the `host:port` target of a `CONNECT` request. Returns `None`
+    /// for other methods or when the port is missing or not a number.
+    pub fn connect_authority(&self) -> Option<(&'a [u8], u16)> {
+        if self.method != b"CONNECT" {
+            return None;
+        }
+        let colon = self.url.iter().rposition(|b| *b == b':')?;
+        let (host, port) = (&self.url[..colon], &self.url[colon + 1..]);
+        if host.is_empty() || port.is_empty() || !port.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let port = core::str::from_utf8(port).ok()?.parse().ok()?;
+        Some((host, port))
+    }
+}
+
+pub(crate) fn percent_decode(input: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        let escaped = match input.get(i..i + 3) {
+            Some([b'%', high, low]) => hex_value(*high).zip(hex_value(*low)),
+            _ => None,
+        };
+        match escaped {
+            Some((high, low)) => {
+                decoded.push(high << 4 | low);
+                i += 3;
+            }
+            None => {
+                decoded.push(input[i]);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|digit| digit as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_request;
+
+    #[test]
+    fn test_query() {
+        let result = parse_request(b"GET /search?q=rust&page=2&raw HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.path(), b"/search");
+        assert_eq!(result.query(), Some(&b"q=rust&page=2&raw"[..]));
+        let params = result.query_params();
+        assert_eq!(params.len(), 3);
+        assert_eq!(params.get(&b"q"[..]), Some(&&b"rust"[..]));
+        assert_eq!(params.get(&b"page"[..]), Some(&&b"2"[..]));
+        assert_eq!(params.get(&b"raw"[..]), Some(&&b""[..]));
+
+        let result = parse_request(b"GET /index HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.path(), b"/index");
+        assert_eq!(result.query(), None);
+        assert!(result.query_params().is_empty());
+    }
+
+    #[test]
+    fn test_form_params() {
+        let input = b"POST /login HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded; charset=utf-8\r\n\
+            Content-Length: 41\r\n\r\nname=J+Doe&note=a%2Bb%26c&flag&&name=Jane";
+        let params = parse_request(input).unwrap().form_params().unwrap();
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[&b"name"[..]], b"Jane");
+        assert_eq!(params[&b"note"[..]], b"a+b&c");
+        assert_eq!(params[&b"flag"[..]], b"");
+
+        let input = b"POST / HTTP/1.1\r\nContent-Type: Application/X-WWW-Form-Urlencoded\r\n\
+            Content-Length: 7\r\n\r\na%20b=c";
+        let params = parse_request(input).unwrap().form_params().unwrap();
+        assert_eq!(params[&b"a b"[..]], b"c");
+
+        let input = b"POST / HTTP/1.1\r\nContent-Type: text/plain\r\nContent-Length: 3\r\n\r\na=b";
+        assert_eq!(parse_request(input).unwrap().form_params(), None);
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 3\r\n\r\na=b";
+        assert_eq!(parse_request(input).unwrap().form_params(), None);
+    }
+
+    #[test]
+    fn test_path_params() {
+        let result =
+            parse_request(b"GET /file;version=2;raw/sub;a=b;;c=?q=1;x=y HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(
+            result.path_params(),
+            [
+                (&b"version"[..], &b"2"[..]),
+                (b"raw", b""),
+                (b"a", b"b"),
+                (b"c", b""),
+            ]
+        );
+        let result = parse_request(b"GET /plain/path?a=1 HTTP/1.1\r\n\r\n").unwrap();
+        assert!(result.path_params().is_empty());
+    }
+
+    #[test]
+    fn test_decoded_path() {
+        let result = parse_request(b"GET /search%20results?q=%41 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.decoded_path(), b"/search results");
+        let result = parse_request(b"GET /a%2fb%ZZ%4 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.decoded_path(), b"/a/b%ZZ%4");
+        let result = parse_request(b"GET /100% HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.decoded_path(), b"/100%");
+    }
+
+    #[test]
+    fn test_normalized_path() {
+        let normalized = |url: &str| {
+            let input = format!("GET {} HTTP/1.1\r\n\r\n", url);
+            parse_request(input.as_bytes()).unwrap().normalized_path()
+        };
+        assert_eq!(normalized("/a//b"), b"/a/b");
+        assert_eq!(normalized("/a/../b"), b"/b");
+        assert_eq!(normalized("/../etc"), b"/etc");
+        assert_eq!(normalized("/a/./b/?q=/../x"), b"/a/b/");
+        assert_eq!(normalized("/a/b/.."), b"/a/");
+        assert_eq!(normalized("/a/%2e%2e/%2E%2E/%2e%2e/passwd"), b"/passwd");
+        assert_eq!(normalized("/"), b"/");
+        assert_eq!(normalized("/.."), b"/");
+    }
+
+    #[test]
+    fn test_host() {
+        let host = |value: &str| {
+            let input = format!("GET / HTTP/1.1\r\nHost: {}\r\n\r\n", value);
+            parse_request(input.as_bytes())
+                .unwrap()
+                .host()
+                .map(|(host, port)| (host.to_vec(), port))
+        };
+        assert_eq!(host("example.com"), Some((b"example.com".to_vec(), None)));
+        assert_eq!(
+            host("example.com:8080"),
+            Some((b"example.com".to_vec(), Some(8080)))
+        );
+        assert_eq!(
+            host("192.0.2.1:80"),
+            Some((b"192.0.2.1".to_vec(), Some(80)))
+        );
+        assert_eq!(host("192.0.2.1"), Some((b"192.0.2.1".to_vec(), None)));
+        assert_eq!(host("[::1]:8080"), Some((b"[::1]".to_vec(), Some(8080))));
+        assert_eq!(
+            host("[2001:db8::1]"),
+            Some((b"[2001:db8::1]".to_vec(), None))
+        );
+        assert_eq!(host("example.com:"), Some((b"example.com".to_vec(), None)));
+        let result = parse_request(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.host(), None);
+    }
+
+    #[test]
+    fn test_host_rejects_invalid() {
+        for value in ["", ":80", "x:http", "x:70000", "[::1", "[::1]x"] {
+            let input = format!("GET / HTTP/1.1\r\nHost: {}\r\n\r\n", value);
+            assert_eq!(
+                parse_request(input.as_bytes()).unwrap().host(),
+                None,
+                "{}",
+                value
+            );
+        }
+        // Colons outside brackets are rejected, not split at either end.
+        for value in ["x:1:2", "::1", "2001:db8::1:80"] {
+            let input = format!("GET / HTTP/1.1\r\nHost: {}\r\n\r\n", value);
+            assert_eq!(
+                parse_request(input.as_bytes()).unwrap().host(),
+                None,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_connect_authority() {
+        let result = parse_request(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.connect_authority(), Some((&b"example.com"[..], 443)));
+        let result = parse_request(b"CONNECT [::1]:8080 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(result.connect_authority(), Some((&b"[::1]"[..], 8080)));
+        for input in [
+            &b"CONNECT example.com HTTP/1.1\r\n\r\n"[..],
+            b"CONNECT example.com: HTTP/1.1\r\n\r\n",
+            b"CONNECT example.com:https HTTP/1.1\r\n\r\n",
+            b"CONNECT example.com:70000 HTTP/1.1\r\n\r\n",
+            b"GET example.com:443 HTTP/1.1\r\n\r\n",
+        ] {
+            assert_eq!(parse_request(input).unwrap().connect_authority(), None);
+        }
+    }
+
+    fn target(input: &[u8]) -> RequestTarget<'_> {
+        parse_request(input).unwrap().request_target()
+    }
+
+    #[test]
+    fn test_request_target() {
+        assert_eq!(
+            target(b"GET /path?q=1 HTTP/1.1\r\n\r\n"),
+            RequestTarget::Origin(b"/path?q=1")
+        );
+        assert_eq!(
+            target(b"GET http://example.com:8080/path?q=1 HTTP/1.1\r\n\r\n"),
+            RequestTarget::Absolute {
+                scheme: b"http",
+                authority: b"example.com:8080",
+                path: b"/path?q=1"
+            }
+        );
+        assert_eq!(
+            target(b"GET https://example.com HTTP/1.1\r\n\r\n"),
+            RequestTarget::Absolute {
+                scheme: b"https",
+                authority: b"example.com",
+                path: b""
+            }
+        );
+        assert_eq!(
+            target(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n"),
+            RequestTarget::Authority(b"example.com:443")
+        );
+        assert_eq!(
+            target(b"OPTIONS * HTTP/1.1\r\n\r\n"),
+            RequestTarget::Asterisk
+        );
+        assert_eq!(
+            target(b"GET index.html HTTP/1.1\r\n\r\n"),
+            RequestTarget::Origin(b"index.html")
+        );
+    }
+}
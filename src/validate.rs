@@ -0,0 +1,47 @@
+use core::fmt;
+
+use crate::{HeaderSink, Request};
+
+/// A request that parsed but breaks a protocol requirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    /// An HTTP/1.1 request without a `Host` header (RFC 7230 §5.4).
+    MissingHost,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::MissingHost => write!(f, "missing host header"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Checks the requirements that parsing alone does not enforce.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let needs_host = self.version().is_some_and(|version| version >= (1, 1));
+        if needs_host && self.header("host").is_none() {
+            return Err(ValidationError::MissingHost);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_request;
+
+    #[test]
+    fn test_validate_host() {
+        let result = parse_request(b"GET / HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(result.validate(), Ok(()));
+        let result = parse_request(b"GET / HTTP/1.1\r\nAccept: */*\r\n\r\n").unwrap();
+        assert_eq!(result.validate(), Err(ValidationError::MissingHost));
+        let result = parse_request(b"GET / HTTP/1.0\r\n\r\n").unwrap();
+        assert_eq!(result.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{trim_whitespace, HeaderSink, Response};
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Lists the request header names in the `Vary` headers, which a cache
+    /// must include in its key. `Vary: *` is returned as the name `*`, which
+    /// no request header matches, so the response cannot be reused.
+    pub fn vary(&self) -> Vec<&[u8]> {
+        self.header_all("vary")
+            .into_iter()
+            .flat_map(|value| value.split(|b| *b == b','))
+            .map(trim_whitespace)
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_response;
+
+    #[test]
+    fn test_vary() {
+        let input =
+            b"HTTP/1.1 200 OK\r\nVary: Accept-Encoding , User-Agent,\r\nvary:Origin\r\n\r\n";
+        let result = parse_response(input).unwrap();
+        assert_eq!(
+            result.vary(),
+            [&b"Accept-Encoding"[..], b"User-Agent", b"Origin"]
+        );
+
+        let result = parse_response(b"HTTP/1.1 200 OK\r\nVary: *\r\n\r\n").unwrap();
+        assert_eq!(result.vary(), [&b"*"[..]]);
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert!(result.vary().is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use core::cmp::Ordering;
+
+use crate::{parse_version, HeaderSink, Request, Response};
+
+/// A protocol version. Versions compare in release order; `Other` is not
+/// ordered relative to anything but itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpVersion {
+    Http09,
+    Http10,
+    Http11,
+    /// Any version the crate does not know about, or an invalid one.
+    Other,
+}
+
+impl HttpVersion {
+    fn rank(self) -> Option<u8> {
+        match self {
+            HttpVersion::Http09 => Some(0),
+            HttpVersion::Http10 => Some(1),
+            HttpVersion::Http11 => Some(2),
+            HttpVersion::Other => None,
+        }
+    }
+}
+
+impl PartialOrd for HttpVersion {
+    fn partial_cmp(&self, other: &HttpVersion) -> Option<Ordering> {
+        match (self.rank(), other.rank()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            (None, None) => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+impl From<&[u8]> for HttpVersion {
+    /// Maps a version string such as `b"HTTP/1.1"` to its variant.
+    fn from(version: &[u8]) -> HttpVersion {
+        match parse_version(version) {
+            Some((0, 9)) => HttpVersion::Http09,
+            Some((1, 0)) => HttpVersion::Http10,
+            Some((1, 1)) => HttpVersion::Http11,
+            _ => HttpVersion::Other,
+        }
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Request<'a, H> {
+    /// Returns the protocol version as an `HttpVersion`.
+    pub fn http_version_enum(&self) -> HttpVersion {
+        HttpVersion::from(self.http_version)
+    }
+}
+
+impl<'a, H: HeaderSink<'a>> Response<'a, H> {
+    /// Returns the protocol version as an `HttpVersion`.
+    pub fn http_version_enum(&self) -> HttpVersion {
+        HttpVersion::from(self.http_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_request, parse_response};
+
+    #[test]
+    fn test_from_bytes() {
+        assert_eq!(HttpVersion::from(&b"HTTP/0.9"[..]), HttpVersion::Http09);
+        assert_eq!(HttpVersion::from(&b"HTTP/1.0"[..]), HttpVersion::Http10);
+        assert_eq!(HttpVersion::from(&b"HTTP/1.1"[..]), HttpVersion::Http11);
+        assert_eq!(HttpVersion::from(&b"HTTP/2.0"[..]), HttpVersion::Other);
+        assert_eq!(HttpVersion::from(&b"http/1.1"[..]), HttpVersion::Other);
+        assert_eq!(HttpVersion::from(&b"garbage"[..]), HttpVersion::Other);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(HttpVersion::Http11 >= HttpVersion::Http11);
+        assert!(HttpVersion::Http11 > HttpVersion::Http10);
+        assert!(HttpVersion::Http09 < HttpVersion::Http10);
+        assert_eq!(HttpVersion::Other.partial_cmp(&HttpVersion::Http11), None);
+        assert!(HttpVersion::Other >= HttpVersion::Other);
+    }
+
+    #[test]
+    fn test_accessors() {
+        let result = parse_request(b"GET / HTTP/1.0\r\n\r\n").unwrap();
+        assert_eq!(result.http_version_enum(), HttpVersion::Http10);
+        let result = parse_response(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+        assert!(result.http_version_enum() >= HttpVersion::Http11);
+    }
+}
@@ -0,0 +1,101 @@
+//! Checks that parsing borrows every field from the input: the only
+//! allocations are for the one header sink, so their number and size do
+//! not depend on how long the fields are.
+
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use http_parser::{parse_request, parse_request_with_headers, Request};
+
+struct Counting;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+fn record(size: usize) {
+    if COUNTING.with(Cell::get) {
+        ALLOCATIONS.with(|allocations| {
+            let (count, bytes) = allocations.get();
+            allocations.set((count + 1, bytes + size));
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Returns the number of allocations `parse` makes for `input` and the
+/// bytes they requested.
+fn allocations_with<'a, H>(
+    input: &'a [u8],
+    parse: impl FnOnce(&'a [u8]) -> Request<'a, H>,
+) -> (usize, usize) {
+    ALLOCATIONS.with(|allocations| allocations.set((0, 0)));
+    COUNTING.with(|counting| counting.set(true));
+    let request = parse(input);
+    COUNTING.with(|counting| counting.set(false));
+    drop(request);
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn allocations(input: &[u8]) -> (usize, usize) {
+    allocations_with(input, |input| parse_request(input).unwrap())
+}
+
+fn hash_map_allocations(input: &[u8]) -> (usize, usize) {
+    allocations_with(input, |input| {
+        parse_request_with_headers::<HashMap<&[u8], &[u8]>>(input).unwrap()
+    })
+}
+
+#[test]
+fn test_parse_request_is_zero_copy() {
+    let short = b"GET /a HTTP/1.1\r\nHost: x\r\nAccept: y\r\nContent-Length: 1\r\n\r\nz";
+    let long = format!(
+        "GET /{} HTTP/1.1\r\nHost: {}\r\nAccept: {}\r\nContent-Length: 1000\r\n\r\n{}",
+        "a".repeat(1000),
+        "x".repeat(1000),
+        "y".repeat(1000),
+        "z".repeat(1000)
+    );
+    let (count, bytes) = allocations(short);
+    assert_eq!(count, 1);
+    assert_eq!(allocations(long.as_bytes()), (count, bytes));
+
+    let bare = allocations(b"GET / HTTP/1.1\r\n\r\n");
+    assert_eq!(bare, (0, 0));
+}
+
+#[test]
+fn test_hash_map_sink_is_only_copy() {
+    let short = b"GET /a HTTP/1.1\r\nHost: x\r\nAccept: y\r\n\r\n";
+    let long = format!(
+        "GET /a HTTP/1.1\r\nHost: {}\r\nAccept: {}\r\n\r\n",
+        "x".repeat(1000),
+        "y".repeat(1000)
+    );
+    let (count, bytes) = hash_map_allocations(short);
+    assert_eq!(count, 1);
+    assert_eq!(hash_map_allocations(long.as_bytes()), (count, bytes));
+}
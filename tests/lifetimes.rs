@@ -0,0 +1,11 @@
+//! Checks that parsed messages borrow from the input buffer and nothing
+//! else.
+
+#![cfg(feature = "std")]
+
+#[test]
+fn test_lifetimes() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/lifetimes/pass_*.rs");
+    t.compile_fail("tests/lifetimes/fail_*.rs");
+}
@@ -0,0 +1,10 @@
+use http_parser::{parse_request, Request};
+
+fn main() {
+    let request: Request;
+    {
+        let data = b"GET / HTTP/1.1\r\n\r\n".to_vec();
+        request = parse_request(&data).unwrap();
+    }
+    assert_eq!(request.url, b"/");
+}
@@ -0,0 +1,13 @@
+use std::sync::Mutex;
+
+use http_parser::{parse_request, Request};
+
+fn parse(buf: &Mutex<Vec<u8>>) -> Request<'_> {
+    let guard = buf.lock().unwrap();
+    parse_request(&guard).unwrap()
+}
+
+fn main() {
+    let buf = Mutex::new(b"GET / HTTP/1.1\r\n\r\n".to_vec());
+    let _ = parse(&buf);
+}
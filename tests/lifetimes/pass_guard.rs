@@ -0,0 +1,28 @@
+use std::sync::Mutex;
+
+use http_parser::{parse_request, ParserConfig, Request};
+
+/// The request only needs the guard's borrow, not the config's or the
+/// mutex's.
+fn url_len(buf: &Mutex<Vec<u8>>) -> usize {
+    let guard = buf.lock().unwrap();
+    let request = {
+        let config = ParserConfig::default();
+        http_parser::parse_request_with_config(&guard, &config).unwrap()
+    };
+    request.url.len()
+}
+
+/// A request outlives any shorter borrow it is coerced to.
+fn shorten<'short, 'long: 'short>(request: Request<'long>) -> Request<'short> {
+    request
+}
+
+fn main() {
+    let buf = Mutex::new(b"GET /index HTTP/1.1\r\n\r\n".to_vec());
+    assert_eq!(url_len(&buf), 6);
+
+    let data = b"GET / HTTP/1.1\r\n\r\n".to_vec();
+    let request = shorten(parse_request(&data).unwrap());
+    assert_eq!(request.url, b"/");
+}